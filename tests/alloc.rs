@@ -0,0 +1,226 @@
+use core::fmt::Write;
+use std::sync::Arc;
+use tranche::{
+    BasedBufTranche, BufTranche, FrameBuffer, LenPrefix, OwnedBufTranche, ResumableError,
+    SharedBufTranche, VecTranche, VecWriter,
+};
+
+#[test]
+fn test_offsets() {
+    let mut writer = VecWriter::from(vec![0xaa]);
+    assert_eq!(writer.put_u16_be(1), 1);
+    assert_eq!(writer.put_i64_le(-2), 3);
+    assert_eq!(writer.put_slice(b"abc"), 11);
+    assert_eq!(writer.offset(), 14);
+
+    let mut tranche = writer.as_tranche();
+    tranche.take_front(3).unwrap();
+    assert_eq!(tranche.offset(), 3);
+    assert_eq!(tranche.take_i64_le().unwrap(), -2);
+    assert_eq!(tranche.offset(), 11);
+}
+
+#[test]
+fn test_fixed_len_prefixes() {
+    let mut writer = VecWriter::new();
+    let le = writer.reserve_len(LenPrefix::U32Le);
+    let be = writer.reserve_len(LenPrefix::U16Be);
+    writer.put_slice(&[7; 300]);
+    assert_eq!(writer.fill_len(le).unwrap(), 302);
+    assert_eq!(writer.fill_len(be).unwrap(), 300);
+
+    let mut tranche = writer.as_tranche();
+    assert_eq!(tranche.take_u32_le().unwrap(), 302);
+    assert_eq!(tranche.take_u16_be().unwrap(), 300);
+    assert_eq!(tranche.len(), 300);
+}
+
+#[test]
+fn test_len_prefix_overflow() {
+    let mut writer = VecWriter::new();
+    let placeholder = writer.reserve_len(LenPrefix::U8);
+    writer.put_slice(&[0; 256]);
+    assert!(writer.fill_len(placeholder).is_err());
+    assert_eq!(writer.written()[0], 0);
+}
+
+#[test]
+fn test_varint_len_prefix() {
+    let mut writer = VecWriter::new();
+    let placeholder = writer.reserve_len(LenPrefix::Varint);
+    assert_eq!(placeholder.body_offset(), 5);
+    writer.put_slice(&[0; 300]);
+    assert_eq!(writer.fill_len(placeholder).unwrap(), 300);
+    assert_eq!(&writer.written()[..5], &[0xac, 0x82, 0x80, 0x80, 0x00]);
+}
+
+#[test]
+fn test_fmt_write() {
+    let mut writer = VecWriter::with_capacity(4);
+    write!(writer, "baguette:{}", 1).unwrap();
+    assert_eq!(writer.into_vec(), b"baguette:1");
+}
+
+#[test]
+fn test_shared_takers() {
+    let mut tranche = SharedBufTranche::from(vec![1, 2, 0, 0, 0, 0, 3, 4]);
+    let clone = tranche.clone();
+
+    assert_eq!(tranche.take_u8().unwrap(), 1);
+    assert_eq!(tranche.take_u16_le().unwrap(), 2);
+    assert_eq!(tranche.take_i32_be().unwrap(), 3);
+    assert_eq!(tranche.offset(), 7);
+
+    let err = tranche.take_u16_be().unwrap_err();
+    assert_eq!(err.needed(), 2);
+    assert_eq!(err.len(), 1);
+    assert_eq!(tranche.as_slice(), &[4]);
+    assert_eq!(clone.len(), 8);
+}
+
+#[test]
+fn test_shared_take_front() {
+    let mut tranche = SharedBufTranche::from(&b"jambon-beurre"[..]);
+    let mut jambon = tranche.take_front(6).unwrap();
+    tranche.take_u8().unwrap();
+    drop(tranche);
+
+    assert!(Arc::ptr_eq(jambon.buffer(), jambon.clone().buffer()));
+    assert_eq!(jambon.as_tranche().as_slice(), b"jambon");
+    let bon = jambon.take_front(5).unwrap().take_front(2);
+    assert!(bon.is_ok());
+    assert_eq!(jambon.as_slice(), b"n");
+
+    let based = jambon.as_based_tranche();
+    assert_eq!(based.offset(), 5);
+    assert_eq!(based.as_slice(), b"n");
+}
+
+#[test]
+fn test_owned_commit() {
+    let mut owned = OwnedBufTranche::from(vec![1, 2, 3, 4, 5]);
+
+    let mut tranche = owned.as_tranche();
+    tranche.take_front(2).unwrap();
+    let taken = owned.len() - tranche.len();
+    owned.advance(taken).unwrap();
+    assert_eq!(owned.offset(), 2);
+
+    let err = owned.advance(4).unwrap_err();
+    assert_eq!(err.needed(), 4);
+    assert_eq!(err.len(), 3);
+
+    let offset = owned.as_based_tranche().offset();
+    assert_eq!(offset, 2);
+    assert!(owned.advance_to(6).is_err());
+    owned.advance_to(5).unwrap();
+    assert!(owned.is_empty());
+    assert_eq!(owned.into_inner().len(), 5);
+}
+
+#[test]
+fn test_owned_parse() {
+    let mut owned = OwnedBufTranche::from(vec![0, 3, 7, 8, 9, 10]);
+    let body = owned
+        .parse(|t| {
+            let len = t.take_u16_be()?;
+            t.take_front(len as usize).map(|body| body.offset())
+        })
+        .unwrap();
+    assert_eq!(body, 2);
+    assert_eq!(owned.offset(), 5);
+
+    assert!(owned.parse(|t| t.take_u16_le()).is_err());
+    assert_eq!(owned.as_slice(), &[10]);
+}
+
+#[test]
+#[should_panic(expected = "tranche was replaced by an unrelated one")]
+fn test_owned_parse_foreign_tranche() {
+    static FOREIGN: [u8; 8] = [0; 8];
+    let mut owned = OwnedBufTranche::from(vec![0; 8]);
+    let _ = owned.parse(|t| {
+        *t = BasedBufTranche::new(&FOREIGN);
+        t.take_u8()
+    });
+}
+
+#[test]
+fn test_vec_tranche_moves() {
+    let strings = ["baguette", "jambon", "beurre", "cornichon", "emmental"];
+    let mut tranche = VecTranche::new(strings.iter().map(|s| s.to_string()).collect());
+
+    assert_eq!(tranche.take_first().unwrap(), "baguette");
+    assert_eq!(tranche.take_last().unwrap(), "emmental");
+
+    let front = tranche.take_front(1).unwrap();
+    assert_eq!(front.into_vec(), ["jambon"]);
+
+    let err = tranche.take_back(3).unwrap_err();
+    assert_eq!(err.needed(), 3);
+    assert_eq!(err.len(), 2);
+
+    let back = tranche.take_back(2).unwrap();
+    assert_eq!(back.as_slice(), ["beurre", "cornichon"]);
+    assert!(tranche.is_empty());
+    assert!(tranche.take_last().is_err());
+}
+
+#[test]
+fn test_vec_tranche_drops_middle() {
+    let counter = Arc::new(());
+    let mut tranche = VecTranche::new(vec![counter.clone(); 5]);
+    let first = tranche.take_first().unwrap();
+    let back = tranche.take_back(2).unwrap();
+    assert_eq!(Arc::strong_count(&counter), 6);
+
+    drop(tranche);
+    assert_eq!(Arc::strong_count(&counter), 4);
+    drop((first, back));
+    assert_eq!(Arc::strong_count(&counter), 1);
+}
+
+fn be_frame(t: &mut BufTranche) -> Result<u32, ResumableError<u16>> {
+    let len = t.take_u16_be()?;
+    if len > 4 {
+        return Err(ResumableError::Malformed(len));
+    }
+    let mut body = t.take_front(len as usize)?;
+    let mut value = 0;
+    while let Ok(byte) = body.take_u8() {
+        value = value << 8 | byte as u32;
+    }
+    Ok(value)
+}
+
+#[test]
+fn test_frame_buffer_byte_by_byte() {
+    let input = [0, 2, 1, 2, 0, 0, 0, 4, 0xde, 0xad, 0xbe, 0xef];
+    let mut buffer = FrameBuffer::new();
+    let mut frames = vec![];
+    for &byte in &input {
+        buffer.extend_from_slice(&[byte]);
+        while let Some(frame) = buffer.next_frame(be_frame).unwrap() {
+            frames.push(frame);
+        }
+    }
+    assert_eq!(frames, [(0x0102, 4), (0, 2), (0xdead_beef, 6)]);
+    assert_eq!(buffer.offset(), 12);
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_frame_buffer_needed_and_malformed() {
+    let mut buffer = FrameBuffer::new();
+    buffer.extend_from_slice(&[0, 3, 1]);
+    assert_eq!(buffer.next_frame(be_frame).unwrap(), None);
+    assert_eq!(buffer.needed(), 2);
+    buffer.extend_from_slice(&[2]);
+    assert_eq!(buffer.needed(), 1);
+
+    buffer.extend_from_slice(&[3, 0, 9]);
+    assert_eq!(buffer.next_frame(be_frame).unwrap(), Some((0x010203, 5)));
+    assert_eq!(buffer.next_frame(be_frame).unwrap_err(), 9);
+    assert_eq!(buffer.as_slice(), &[0, 9]);
+    assert_eq!(buffer.offset(), 5);
+}
@@ -1,4 +1,7 @@
-use tranche::BasedTranche;
+use core::convert::TryFrom;
+use tranche::{
+    BasedBufTranche, BasedTranche, ErrorSpan, ExpectError, Flags, TakeEnumError, TakeLenError,
+};
 
 fn do_test<T>(slice: &impl AsRef<[T]>) {
     let mut tranche = BasedTranche::new(slice);
@@ -7,8 +10,31 @@ fn do_test<T>(slice: &impl AsRef<[T]>) {
     tranche.take_first().unwrap();
     assert_eq!(tranche.offset(), 1);
 
-    tranche.take_front(2).unwrap();
+    let front = tranche.take_front(2).unwrap();
     assert_eq!(tranche.offset(), 3);
+    assert_eq!(front.offset(), 1);
+    assert_eq!(front.len(), 2);
+    assert_eq!(front.extent(), 3);
+
+    tranche.seek_to(5).unwrap();
+    assert_eq!(tranche.offset(), 5);
+    assert_eq!(tranche.len(), 1);
+    assert_eq!(
+        tranche.seek_to(7).unwrap_err().to_string(),
+        "unexpected end at offset 0x0 (needed 7, got 6)",
+    );
+    assert_eq!(tranche.offset(), 5);
+
+    tranche.rewind();
+    assert_eq!(tranche.offset(), 0);
+    assert_eq!(tranche.len(), 6);
+    assert_eq!(tranche.extent(), 6);
+
+    let mut front = front;
+    front.seek_to(1).unwrap();
+    assert_eq!(front.offset(), 1);
+    assert_eq!(front.len(), 2);
+    assert!(front.seek_to(4).is_err());
 }
 
 #[test]
@@ -25,3 +51,224 @@ fn test_words() {
 fn test_units() {
     do_test(&[(), (), (), (), (), ()]);
 }
+
+#[test]
+fn test_error_offsets() {
+    let bytes = [0u8; 10];
+    let mut tranche = BasedTranche::new(&bytes);
+    tranche.take_front(8).unwrap();
+    let err = tranche.take_front(3).unwrap_err();
+    assert_eq!((err.offset(), err.needed(), err.len()), (Some(8), 3, 2));
+
+    let mut tranche = tranche.take_front(2).unwrap();
+    tranche.take_first().unwrap();
+    let err = tranche.take_front(2).unwrap_err();
+    assert_eq!(err.offset(), Some(9));
+    tranche.take_first().unwrap();
+    assert_eq!(tranche.take_first().unwrap_err().offset(), Some(10));
+}
+
+#[test]
+fn test_parse_exact() {
+    let bytes = [1u8, 2, 3, 4, 5];
+    let mut tranche = BasedTranche::new(&bytes);
+    tranche.take_first().unwrap();
+
+    let offsets = tranche
+        .parse_exact(2, |sub| {
+            let start = sub.offset();
+            sub.take_front(2)?;
+            Ok::<_, tranche::Error>((start, sub.offset()))
+        })
+        .unwrap();
+    assert_eq!(offsets, (1, 3));
+    assert_eq!(tranche.offset(), 3);
+
+    let err = tranche
+        .parse_exact(2, |sub| sub.take_first().map_err(tranche::Error::from))
+        .unwrap_err();
+    match err {
+        tranche::Error::TrailingData(err) => {
+            assert_eq!((err.len(), err.offset()), (1, Some(4)));
+        }
+        _ => unreachable!(),
+    }
+    assert_eq!(tranche.offset(), 3);
+
+    let err = tranche.parse_exact(3, |_| Ok::<_, tranche::Error>(()));
+    assert!(matches!(err, Err(tranche::Error::UnexpectedEnd(_))));
+
+    let err = tranche
+        .parse_all(|t| t.take_first().map_err(tranche::Error::from))
+        .unwrap_err();
+    assert_eq!(err.to_string(), "trailing data at offset 0x4 (1 left)");
+    assert!(tranche
+        .parse_all(|t| t.take_first().map_err(tranche::Error::from))
+        .is_ok());
+    assert!(tranche.finish().is_ok());
+}
+
+#[test]
+fn test_expect() {
+    let mut tranche = BasedBufTranche::new(b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR");
+    tranche.expect_u32_be(0x89504e47).unwrap();
+    assert!(tranche.eat_slice(b"\r\n"));
+    assert!(!tranche.eat(&b'\n'));
+    tranche.expect(b"\x1a\n").unwrap();
+    assert_eq!(tranche.offset(), 8);
+
+    match tranche.expect_u32_le(13).unwrap_err() {
+        ExpectError::Mismatch(err) => {
+            assert_eq!((*err.expected(), *err.found()), (13, 0x0d000000));
+            assert_eq!(err.offset(), Some(8));
+            assert_eq!(err.span(), Some(8..12));
+        }
+        _ => unreachable!(),
+    }
+    assert_eq!(tranche.offset(), 8);
+    tranche.expect_u32_be(13).unwrap();
+
+    let err = tranche.expect(b"IHDRx").unwrap_err();
+    assert!(matches!(&err, ExpectError::UnexpectedEnd(end) if end.offset() == Some(12)));
+    let err = tranche.expect(b"IDAT").unwrap_err();
+    assert_eq!(err.span(), Some(12..16));
+    let err = tranche::Error::from(err);
+    assert_eq!(err.span(), Some(12..16));
+    assert_eq!(
+        err.to_string(),
+        "mismatch at offset 0xc (expected [49, 44, 41, 54], found [49, 48, 44, 52])"
+    );
+    assert_eq!(tranche.offset(), 12);
+}
+
+#[test]
+fn test_expect_units() {
+    let units = [(); 4];
+    let mut tranche = BasedTranche::new(&units);
+    assert!(tranche.eat(&()));
+    tranche.expect(&[(), ()]).unwrap();
+    assert_eq!(tranche.offset(), 3);
+    let err = tranche.expect(&[(), ()]).unwrap_err();
+    assert!(matches!(err, ExpectError::UnexpectedEnd(end) if end.offset() == Some(3)));
+
+    let mut tranche = tranche::Tranche::new(&units);
+    tranche.expect_back(&[(), (), ()]).unwrap();
+    assert_eq!(tranche.len(), 1);
+    assert!(tranche.expect_back(&[(), ()]).is_err());
+    assert_eq!(tranche.len(), 1);
+}
+
+#[test]
+fn test_take_len() {
+    let mut tranche = BasedBufTranche::new(b"\0\0\0\x02ab\x03\0\0\0\0\0\0\0xyz\x01");
+    let len = tranche.take_u32_be_as_len().unwrap();
+    assert_eq!(tranche.take_front(len).unwrap().as_slice(), b"ab");
+    let len = tranche.take_u64_le_as_len().unwrap();
+    assert_eq!(
+        tranche.take_front_u64(len as u64).unwrap().as_slice(),
+        b"xyz"
+    );
+
+    let err = tranche.take_u16_be_as_len().unwrap_err();
+    assert!(matches!(&err, TakeLenError::UnexpectedEnd(end) if end.offset() == Some(17)));
+    assert_eq!(tranche.offset(), 17);
+    let err = tranche.take_front_u64(2).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "unexpected end at offset 0x11 (needed 2, got 1)"
+    );
+
+    let err = tranche.take_front_count(usize::MAX / 2, 3).unwrap_err();
+    assert!(matches!(&err, TakeLenError::Overflow(overflow) if overflow.offset() == Some(17)));
+    assert_eq!(err.to_string(), "integer overflow at offset 0x11");
+    assert_eq!(tranche.take_front_count(1, 1).unwrap().as_slice(), b"\x01");
+}
+
+#[cfg(target_pointer_width = "32")]
+#[test]
+fn test_take_len_overflow() {
+    let mut tranche = BasedBufTranche::new(b"\0\0\0\x01\0\0\0\0");
+    let err = tranche.take_u64_be_as_len().unwrap_err();
+    assert_eq!(err.span(), Some(0..8));
+    assert_eq!(tranche.offset(), 0);
+    assert!(tranche.take_front_u64(1 << 32).is_err());
+}
+
+#[derive(Debug, PartialEq)]
+enum Tag {
+    Int,
+    Str,
+}
+
+impl TryFrom<u16> for Tag {
+    type Error = ();
+
+    fn try_from(value: u16) -> Result<Self, ()> {
+        match value {
+            1 => Ok(Tag::Int),
+            2 => Ok(Tag::Str),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Mode(u16);
+
+impl Flags for Mode {
+    type Bits = u16;
+
+    fn from_bits(bits: u16) -> Option<Self> {
+        if bits & !0x0f0f == 0 {
+            Some(Mode(bits))
+        } else {
+            None
+        }
+    }
+
+    fn from_bits_retain(bits: u16) -> Self {
+        Mode(bits)
+    }
+}
+
+#[test]
+fn test_take_enum() {
+    let mut tranche = BasedBufTranche::new(&[2, 0, 7, 0, 1, 0xf0, 1]);
+    let tag: Tag = tranche.take_enum(BasedBufTranche::take_u16_le).unwrap();
+    assert_eq!(tag, Tag::Str);
+
+    let err = tranche
+        .take_enum::<Tag, _>(BasedBufTranche::take_u16_le)
+        .unwrap_err();
+    assert_eq!(err.to_string(), "invalid discriminant 0x7 at offset 0x2");
+    assert_eq!(err.span(), Some(2..4));
+    let err = tranche::Error::from(err);
+    assert_eq!(err.to_string(), "invalid discriminant 0x7 at offset 0x2");
+    match &err {
+        tranche::Error::InvalidDiscriminant(err) => assert_eq!(*err.value(), 7),
+        _ => unreachable!(),
+    }
+    assert_eq!(err.span(), Some(2..4));
+    assert_eq!(tranche.offset(), 2);
+    tranche.take_u16_le().unwrap();
+
+    let err = tranche
+        .take_flags::<Mode>(BasedBufTranche::take_u16_le)
+        .unwrap_err();
+    match err {
+        TakeEnumError::InvalidDiscriminant(err) => {
+            assert_eq!((*err.value(), err.offset()), (0xf001, Some(4)));
+        }
+        _ => unreachable!(),
+    }
+    let mode: Mode = tranche
+        .take_flags_retain(BasedBufTranche::take_u16_le)
+        .unwrap();
+    assert_eq!(mode, Mode(0xf001));
+
+    let err = tranche
+        .take_enum::<Tag, _>(BasedBufTranche::take_u16_le)
+        .unwrap_err();
+    assert!(matches!(err, TakeEnumError::UnexpectedEnd(end) if end.offset() == Some(6)));
+    assert_eq!(tranche.offset(), 6);
+}
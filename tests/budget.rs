@@ -0,0 +1,50 @@
+use tranche::{BasedBufTranche, Budget, BufTranche, Error, Limit};
+
+#[test]
+fn test_limits() {
+    let mut budget = Budget::new(BufTranche::new(&[1, 2, 3]))
+        .with_max_elements(10)
+        .with_max_alloc(100);
+    assert_eq!(budget.count(6).unwrap(), 6);
+    let err = budget.count(5).unwrap_err();
+    assert_eq!((err.limit(), err.max()), (Limit::Elements, 10));
+    assert_eq!(budget.elements(), 6);
+    budget.count(4).unwrap();
+    assert!(budget.count(usize::MAX).is_err());
+
+    budget.reserve(64).unwrap();
+    let err = budget.reserve(64).unwrap_err();
+    assert_eq!(err.to_string(), "allocation limit of 100 exceeded");
+    assert_eq!(budget.allocated(), 64);
+
+    assert_eq!(budget.take_u8().unwrap(), 1);
+    assert_eq!(budget.into_inner().as_slice(), &[2, 3]);
+}
+
+#[test]
+fn test_depth() {
+    let mut budget = Budget::new(BasedBufTranche::new(&[0; 4])).with_max_depth(2);
+    budget.enter().unwrap();
+    budget.enter().unwrap();
+    let err = budget.enter().unwrap_err();
+    assert_eq!(err.limit(), Limit::Depth);
+    assert_eq!(budget.depth(), 2);
+    budget.exit();
+    budget.exit();
+    budget.exit();
+    assert_eq!(budget.depth(), 0);
+
+    let err = budget
+        .nested(|b| {
+            b.take_front(2)?;
+            b.nested(|b| b.take_u32_le().map_err(Error::from))
+        })
+        .unwrap_err();
+    assert!(matches!(err, Error::UnexpectedEnd(end) if end.offset() == Some(2)));
+    assert_eq!(budget.depth(), 0);
+    assert_eq!(budget.offset(), 2);
+
+    let res = budget.nested(|b| b.nested(|b| b.nested(|_| Ok::<_, Error>(()))));
+    assert!(matches!(res, Err(Error::LimitExceeded(_))));
+    assert_eq!(budget.depth(), 0);
+}
@@ -0,0 +1,37 @@
+use bytes::{Buf, Bytes};
+use static_assertions::assert_impl_all;
+use tranche::{BasedBufTranche, BufTranche};
+
+#[allow(clippy::extra_unused_lifetimes)]
+fn _buf<'a>() {
+    assert_impl_all!(BufTranche<'a>, Buf);
+    assert_impl_all!(BasedBufTranche<'a>, Buf);
+}
+
+fn sum_u16s(mut buf: impl Buf) -> u32 {
+    let mut sum = 0;
+    while buf.remaining() >= 2 {
+        sum += buf.get_u16() as u32;
+    }
+    sum
+}
+
+#[test]
+fn test_buf() {
+    let bytes = Bytes::from_static(&[0, 1, 0, 2, 1, 0, 0xff]);
+    assert_eq!(sum_u16s(BufTranche::from(&bytes)), 0x103);
+
+    let mut tranche = BasedBufTranche::from(&bytes);
+    assert_eq!(tranche.chunk(), &bytes[..]);
+    tranche.advance(2);
+    assert_eq!(tranche.offset(), 2);
+    assert_eq!(tranche.copy_to_bytes(3), Bytes::from_static(&[0, 2, 1]));
+    assert_eq!(tranche.offset(), 5);
+    assert_eq!(tranche.remaining(), 2);
+}
+
+#[test]
+#[should_panic]
+fn test_advance_past_end() {
+    BufTranche::new(&[1, 2]).advance(3);
+}
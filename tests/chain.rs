@@ -0,0 +1,61 @@
+use tranche::{BufTranche, ChainedBufTranche, ChainedFront};
+
+#[test]
+fn test_takers_across_segments() {
+    let segments: [&[u8]; 5] = [&[], &[1, 0], &[0], &[0, 2, 0xaa, 0xbb], &[0xcc]];
+    let mut tranche = ChainedBufTranche::new(&segments);
+    assert_eq!(tranche.len(), 8);
+
+    assert_eq!(tranche.take_u32_le().unwrap(), 1);
+    assert_eq!(tranche.offset(), 4);
+    assert_eq!(tranche.take_u8().unwrap(), 2);
+    assert_eq!(tranche.chunk(), &[0xaa, 0xbb]);
+
+    let err = tranche.take_u32_be().unwrap_err();
+    assert_eq!(err.needed(), 4);
+    assert_eq!(err.len(), 3);
+    assert_eq!(tranche.offset(), 5);
+
+    assert_eq!(tranche.take_u16_be().unwrap(), 0xaabb);
+    assert_eq!(tranche.take_i8().unwrap(), 0xcc_u8 as i8);
+    assert!(tranche.is_empty());
+    assert_eq!(tranche.chunks().count(), 0);
+}
+
+#[test]
+fn test_take_front() {
+    let segments: [&[u8]; 3] = [b"baguette", b"jambon", b"beurre"];
+    let mut tranche = ChainedBufTranche::new(&segments);
+
+    let mut sandwich = match tranche.take_front(19).unwrap() {
+        ChainedFront::Chained(sandwich) => sandwich,
+        ChainedFront::Contiguous(_) => panic!("expected a chained front"),
+    };
+    assert_eq!(format!("{:?}", tranche), "[101]");
+    assert_eq!(tranche.offset(), 19);
+
+    assert_eq!(sandwich.offset(), 0);
+    assert_eq!(
+        sandwich.chunks().collect::<Vec<_>>(),
+        [&b"baguette"[..], b"jambon", b"beurr"],
+    );
+    match sandwich.take_front(8).unwrap() {
+        ChainedFront::Contiguous(baguette) => assert_eq!(baguette.as_slice(), b"baguette"),
+        ChainedFront::Chained(_) => panic!("expected a contiguous front"),
+    }
+    let rest = sandwich.take_front(11).unwrap().into_chained();
+    assert_eq!(rest.offset(), 8);
+    assert_eq!(rest.len(), 11);
+    assert!(sandwich.is_empty());
+    assert!(sandwich.take_u8().is_err());
+}
+
+#[test]
+fn test_from_tranche() {
+    let mut tranche = ChainedBufTranche::from(BufTranche::new(&[1, 2, 3]));
+    let mut dest = [0; 2];
+    tranche.take_into(&mut dest).unwrap();
+    assert_eq!(dest, [1, 2]);
+    assert!(tranche.take_into(&mut dest).is_err());
+    assert_eq!(tranche.len(), 1);
+}
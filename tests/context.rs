@@ -0,0 +1,44 @@
+use tranche::{BufTranche, Context, ContextError, Error, InvalidValueError};
+
+fn nested(t: &mut BufTranche, depth: usize) -> Result<u8, ContextError> {
+    if depth == 0 {
+        return t.take_u8().context("leaf");
+    }
+    nested(t, depth - 1).context_index("level", depth)
+}
+
+#[test]
+fn test_labels() {
+    let err = nested(&mut BufTranche::new(&[]), 1).unwrap_err();
+    let labels = err
+        .labels()
+        .map(|l| (l.name(), l.index()))
+        .collect::<Vec<_>>();
+    assert_eq!(labels, [("level", Some(1)), ("leaf", None)]);
+    assert!(!err.is_truncated());
+    assert!(matches!(err.error(), Error::UnexpectedEnd(_)));
+    assert_eq!(
+        err.to_string(),
+        "level[1] > leaf: unexpected end (needed 1, got 0)",
+    );
+}
+
+#[test]
+fn test_without_labels() {
+    let err = ContextError::from(InvalidValueError::new("magic"));
+    assert_eq!(err.to_string(), "invalid magic");
+    assert!(matches!(err.into_error(), Error::InvalidValue(_)));
+}
+
+#[test]
+fn test_deep_nesting() {
+    let err = nested(&mut BufTranche::new(&[]), 10).unwrap_err();
+    let innermost = err.labels().last().unwrap();
+    assert_eq!(innermost.name(), "leaf");
+    if !err.is_truncated() {
+        assert_eq!(err.labels().count(), 11);
+    } else {
+        assert_eq!(err.labels().count(), 2);
+        assert!(err.to_string().starts_with("... > level[1] > leaf: "));
+    }
+}
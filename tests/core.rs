@@ -1,10 +1,29 @@
+use core::error;
 use core::fmt;
-use static_assertions::{assert_eq_size, assert_eq_type, assert_impl_all, assert_not_impl_any};
-use tranche::{BasedBufTranche, BasedTranche, BufTranche, Tranche, UnexpectedEndError};
+use static_assertions::{assert_eq_size, assert_impl_all, assert_not_impl_any};
+use tranche::{
+    BasedBufTranche, BasedTranche, BufTranche, ChainedBufTranche, ChainedFront, ContextError,
+    Error, ExpectError, InvalidDiscriminantError, InvalidValueError, LimitExceededError,
+    MisalignedError, MismatchError, OverflowError, ResumableError, SliceWriter, TakeAsError,
+    TakeEnumError, TakeLenError, TrailingDataError, Tranche, UnexpectedEndError,
+};
 
 #[test]
 fn it_compiled() {}
 
+#[test]
+fn test_zst_take_front() {
+    let units = [(); 5];
+    let mut tranche = Tranche::new(&units);
+    let front = tranche.take_front(3).unwrap();
+    assert_eq!(front.len(), 3);
+    assert_eq!(front.as_slice(), &[(), (), ()]);
+    assert_eq!(tranche.len(), 2);
+    assert!(tranche.take_front(3).is_err());
+    assert_eq!(tranche.take_front(2).unwrap().len(), 2);
+    assert!(tranche.is_empty());
+}
+
 fn _sizes<'a, T>()
 where
     T: 'a,
@@ -15,9 +34,11 @@ where
     assert_eq_size!(Option<BasedTranche<'a, T>>, BasedTranche<'a, T>);
 }
 
-fn _buf_tranche<'a>() {
-    assert_eq_type!(BufTranche<'a>, Tranche<'a, u8>);
-    assert_eq_type!(BasedBufTranche<'a>, BasedTranche<'a, u8>);
+fn _buf_tranche<'a>(
+    buf: Tranche<'a, u8>,
+    based: BasedTranche<'a, u8>,
+) -> (BufTranche<'a>, BasedBufTranche<'a>) {
+    (buf, based)
 }
 
 fn _tranche_sync<T: Sync>() {
@@ -48,7 +69,77 @@ fn _tranche_iter<T>() {
     assert_impl_all!(BasedTranche<T>, Iterator);
 }
 
+fn _chained_tranche() {
+    assert_impl_all!(ChainedBufTranche, Clone, Default, fmt::Debug, Send, Sync);
+    assert_impl_all!(ChainedFront, Clone, fmt::Debug, Send, Sync);
+}
+
+fn _slice_writer() {
+    assert_impl_all!(SliceWriter, fmt::Debug, fmt::Write, Send, Sync);
+    assert_not_impl_any!(SliceWriter, Clone);
+}
+
 fn _error() {
+    assert_impl_all!(
+        Error,
+        Clone,
+        fmt::Debug,
+        fmt::Display,
+        From<UnexpectedEndError>,
+        From<TakeAsError>,
+        Send,
+        Sync,
+        error::Error,
+    );
+    assert_impl_all!(InvalidValueError, error::Error, Send, Sync);
+    assert_impl_all!(
+        ContextError,
+        Clone,
+        fmt::Debug,
+        fmt::Display,
+        From<UnexpectedEndError>,
+        From<Error>,
+        Send,
+        Sync,
+        error::Error,
+    );
+    assert_impl_all!(TrailingDataError, error::Error, Send, Sync);
+    assert_impl_all!(LimitExceededError, Clone, error::Error, Send, Sync);
+    assert_impl_all!(
+        TakeEnumError<u16>,
+        Clone,
+        fmt::Debug,
+        fmt::Display,
+        From<UnexpectedEndError>,
+        From<InvalidDiscriminantError<u16>>,
+        Send,
+        Sync,
+        error::Error,
+    );
+    assert_impl_all!(
+        ExpectError<u32>,
+        Clone,
+        fmt::Debug,
+        fmt::Display,
+        From<UnexpectedEndError>,
+        From<MismatchError<u32>>,
+        Send,
+        Sync,
+        error::Error,
+    );
+    assert_impl_all!(MismatchError<&'static [u8]>, error::Error, Send, Sync);
+    assert_impl_all!(
+        TakeLenError,
+        Clone,
+        fmt::Debug,
+        fmt::Display,
+        From<UnexpectedEndError>,
+        From<OverflowError>,
+        Send,
+        Sync,
+        error::Error,
+    );
+    assert_impl_all!(UnexpectedEndError, error::Error);
     assert_impl_all!(
         UnexpectedEndError,
         Clone,
@@ -57,4 +148,25 @@ fn _error() {
         Send,
         Sync,
     );
+    assert_impl_all!(MisalignedError, Clone, fmt::Debug, fmt::Display, Send, Sync);
+    assert_impl_all!(TakeAsError, Clone, fmt::Debug, fmt::Display, Send, Sync);
+    assert_impl_all!(
+        ResumableError<UnexpectedEndError>,
+        Clone,
+        fmt::Debug,
+        fmt::Display,
+        From<UnexpectedEndError>,
+        Send,
+        Sync,
+    );
+}
+
+#[cfg(feature = "alloc")]
+fn _owned_tranches() {
+    use tranche::{OwnedBufTranche, SharedBufTranche, VecTranche};
+
+    assert_impl_all!(SharedBufTranche, Clone, Default, fmt::Debug, Send, Sync);
+    assert_impl_all!(OwnedBufTranche, Clone, Default, fmt::Debug, Send, Sync);
+    assert_impl_all!(VecTranche<String>, Clone, Default, fmt::Debug, Send, Sync);
+    assert_impl_all!(VecTranche<String>, DoubleEndedIterator, ExactSizeIterator);
 }
@@ -0,0 +1,61 @@
+use embedded_io::{BufRead, Error, ErrorKind, Read, ReadExactError, Write};
+use static_assertions::assert_impl_all;
+use tranche::{BasedBufTranche, BufTranche, SliceWriter, UnexpectedEndError};
+
+#[allow(clippy::extra_unused_lifetimes)]
+fn _embedded_io<'a>() {
+    assert_impl_all!(BufTranche<'a>, BufRead, Read);
+    assert_impl_all!(BasedBufTranche<'a>, BufRead, Read);
+    assert_impl_all!(SliceWriter<'a>, Write);
+    assert_impl_all!(UnexpectedEndError, Error);
+}
+
+fn checksum<R: Read>(reader: &mut R) -> Result<u8, R::Error> {
+    let mut buf = [0; 3];
+    let mut sum = 0u8;
+    loop {
+        match reader.read(&mut buf)? {
+            0 => return Ok(sum),
+            n => sum = buf[..n].iter().fold(sum, |sum, &b| sum.wrapping_add(b)),
+        }
+    }
+}
+
+#[test]
+fn test_read() {
+    let mut tranche = BasedBufTranche::from(BufTranche::new(&[1, 2, 3, 4, 5]));
+    assert_eq!(checksum(&mut tranche).unwrap(), 15);
+    assert_eq!(tranche.offset(), 5);
+
+    let mut tranche = BufTranche::new(b"jambon");
+    let mut buf = [0; 4];
+    tranche.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"jamb");
+    assert!(matches!(
+        tranche.read_exact(&mut buf),
+        Err(ReadExactError::UnexpectedEof)
+    ));
+    assert!(tranche.is_empty());
+}
+
+#[test]
+fn test_buf_read() {
+    let mut tranche = BufTranche::new(b"beurre");
+    assert_eq!(tranche.fill_buf().unwrap(), b"beurre");
+    tranche.consume(2);
+    assert_eq!(tranche.fill_buf().unwrap(), b"urre");
+    tranche.consume(10);
+    assert!(tranche.fill_buf().unwrap().is_empty());
+}
+
+#[test]
+fn test_write() {
+    let mut buf = [0; 4];
+    let mut writer = SliceWriter::new(&mut buf);
+    assert_eq!(writer.write(b"abc").unwrap(), 3);
+    assert_eq!(writer.write(b"de").unwrap(), 1);
+    assert_eq!(writer.write(b"").unwrap(), 0);
+    let err = writer.write_all(b"f").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::WriteZero);
+    assert_eq!(writer.written(), b"abcd");
+}
@@ -0,0 +1,119 @@
+use core::error::Error as _;
+use tranche::{
+    BasedBufTranche, BufTranche, Context, Error, ExpectError, InvalidValueError, TakeAsError,
+    Tranche,
+};
+
+#[test]
+fn test_conversions() {
+    let err = Error::from(BufTranche::new(&[1]).take_u32_le().unwrap_err());
+    assert!(matches!(&err, Error::UnexpectedEnd(end) if end.needed() == 4 && end.len() == 1));
+    assert_eq!(err.to_string(), "unexpected end (needed 4, got 1)");
+    assert!(err.source().is_none());
+
+    let bytes = BufTranche::new(&[b'a', 0xff]).as_slice();
+    let err = Error::from(core::str::from_utf8(bytes).unwrap_err());
+    assert!(matches!(err, Error::InvalidUtf8(_)));
+}
+
+#[test]
+fn test_display() {
+    let err = Error::from(InvalidValueError::new("chunk type"));
+    assert_eq!(err.to_string(), "invalid chunk type");
+
+    let err = Error::from(BufTranche::new(&[1, 2, 3]).finish().unwrap_err());
+    assert_eq!(err.to_string(), "trailing data (3 left)");
+    match err {
+        Error::TrailingData(trailing) => assert_eq!(trailing.len(), 3),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn test_take_as_error() {
+    let err = BufTranche::new(&[])
+        .take_front(1)
+        .map_err(TakeAsError::from);
+    assert!(matches!(
+        Error::from(err.unwrap_err()),
+        Error::UnexpectedEnd(_)
+    ));
+}
+
+#[test]
+fn test_expect_error() {
+    let mut tranche = Tranche::new(&[1u16, 2, 3]);
+    let err = tranche.expect(&[1, 3]).unwrap_err();
+    assert_eq!(err.to_string(), "mismatch (expected [1, 3], found [1, 2])");
+    assert!(err.source().is_none());
+    match Error::from(err) {
+        Error::Mismatch(err) => {
+            assert_eq!(err.expected().as_str(), "[1, 3]");
+            assert_eq!(err.found().as_str(), "[1, 2]");
+        }
+        _ => unreachable!(),
+    }
+
+    let long = [0xabu8; 32];
+    let err = Error::from(BufTranche::new(&[0; 32]).expect(&long).unwrap_err());
+    match &err {
+        Error::Mismatch(mismatch) => assert!(mismatch.expected().is_truncated()),
+        _ => unreachable!(),
+    }
+    assert!(err.to_string().starts_with("mismatch (expected [ab, ab, "));
+    assert!(err.to_string().contains(".., found [0, 0, "));
+
+    let err = tranche.expect_back(&[0, 1, 2, 3]).unwrap_err();
+    assert!(matches!(err, ExpectError::UnexpectedEnd(_)));
+    let err = tranche.expect_back(&[9, 2, 3]).unwrap_err();
+    match err {
+        ExpectError::Mismatch(err) => assert_eq!(*err.found(), &[1, 2, 3]),
+        _ => unreachable!(),
+    }
+    tranche.expect_back(&[3]).unwrap();
+    assert_eq!(tranche.as_slice(), &[1, 2]);
+
+    let err = BufTranche::new(&[0xfe]).expect_i8(-1).unwrap_err();
+    assert_eq!(err.to_string(), "mismatch (expected ff, found fe)");
+    let err = BufTranche::new(&[0xfe]).expect_u16_be(1).unwrap_err();
+    assert!(matches!(Error::from(err), Error::UnexpectedEnd(_)));
+}
+
+fn assert_no_repeated_messages(err: &dyn core::error::Error) {
+    let mut messages = vec![err.to_string()];
+    let mut source = err.source();
+    while let Some(err) = source {
+        let message = err.to_string();
+        assert!(
+            messages.iter().all(|outer| !outer.contains(&message)),
+            "{:?} repeats {:?}",
+            messages,
+            message,
+        );
+        messages.push(message);
+        source = err.source();
+    }
+}
+
+#[test]
+fn test_source_chains() {
+    let mut tranche = BasedBufTranche::new(&[0x2a, 1]);
+    assert_no_repeated_messages(&tranche.take_u32_le().unwrap_err());
+    assert_no_repeated_messages(&Error::from(tranche.take_u32_le().unwrap_err()));
+    assert_no_repeated_messages(&tranche.expect(b"x").unwrap_err());
+    assert_no_repeated_messages(&tranche.take_front_count(usize::MAX, 2).unwrap_err());
+    assert_no_repeated_messages(
+        &tranche
+            .take_enum::<bool, _>(BasedBufTranche::take_u8)
+            .unwrap_err(),
+    );
+    assert_no_repeated_messages(
+        &tranche
+            .take_front(3)
+            .map_err(TakeAsError::from)
+            .unwrap_err(),
+    );
+    assert_no_repeated_messages(&tranche.take_front(3).context("body").unwrap_err());
+    let err = tranche::ResumableError::<Error>::Malformed(InvalidValueError::new("tag").into());
+    assert_no_repeated_messages(&err);
+}
@@ -0,0 +1,58 @@
+use futures::executor::block_on;
+use futures::io::{
+    AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt,
+};
+use static_assertions::assert_impl_all;
+use tranche::{BasedBufTranche, BufTranche, SliceWriter, VecWriter};
+
+#[allow(clippy::extra_unused_lifetimes)]
+fn _async<'a>() {
+    assert_impl_all!(BufTranche<'a>, AsyncBufRead, AsyncRead);
+    assert_impl_all!(BasedBufTranche<'a>, AsyncBufRead, AsyncRead);
+    assert_impl_all!(SliceWriter<'a>, AsyncWrite);
+    assert_impl_all!(VecWriter, AsyncWrite);
+}
+
+#[test]
+fn test_async_read() {
+    block_on(async {
+        let mut tranche = BasedBufTranche::from(BufTranche::new(b"jambon\nbeurre"));
+        let mut buf = [0; 3];
+        tranche.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"jam");
+
+        let payload = b"jambon\nbeurre".as_ptr();
+        let filled = tranche.fill_buf().await.unwrap();
+        assert_eq!(filled, b"bon\nbeurre");
+        assert_eq!(filled.as_ptr(), payload.wrapping_add(3));
+
+        let mut line = String::new();
+        tranche.read_line(&mut line).await.unwrap();
+        assert_eq!(line, "bon\n");
+        assert_eq!(tranche.offset(), 7);
+
+        let mut rest = vec![];
+        tranche.read_to_end(&mut rest).await.unwrap();
+        assert_eq!(rest, b"beurre");
+    });
+}
+
+#[test]
+fn test_async_write() {
+    block_on(async {
+        let mut buf = [0; 4];
+        let mut writer = SliceWriter::new(&mut buf);
+        writer.write_all(b"abc").await.unwrap();
+        assert!(writer.write_all(b"de").await.is_err());
+        writer.close().await.unwrap();
+        assert_eq!(writer.written(), b"abcd");
+
+        let mut writer = VecWriter::new();
+        let mut tranche = BufTranche::new(b"baguette");
+        futures::io::copy_buf(&mut tranche, &mut writer)
+            .await
+            .unwrap();
+        writer.flush().await.unwrap();
+        assert_eq!(writer.written(), b"baguette");
+    });
+}
@@ -0,0 +1,72 @@
+use tranche::{BasedBufTranche, BufTranche, Context, ErrorSpan, Hexdump, InvalidValueError};
+
+#[test]
+fn test_context_rows() {
+    let buf = (0..40).collect::<Vec<u8>>();
+    let mut tranche = BasedBufTranche::from(BufTranche::new(&buf));
+    tranche.take_front(20).unwrap();
+    let err = tranche.take_front(30).context("body").unwrap_err();
+    assert_eq!(err.span(), Some(20..50));
+
+    let report = Hexdump::new(&buf, &err)
+        .with_context(2)
+        .with_row_width(8)
+        .to_string();
+    let expected = [
+        "error: body: unexpected end at offset 0x14 (needed 30, got 20)",
+        "00000010  10 11 12 13 14 15 16 17  |........|",
+        "                      ^^ ^^ ^^ ^^",
+        "00000018  18 19 1a 1b 1c 1d 1e 1f  |........|",
+        "          ^^ ^^ ^^ ^^ ^^ ^^ ^^ ^^",
+        "00000020  20 21 22 23 24 25 26 27  | !\"#$%&'|",
+        "          ^^ ^^ ^^ ^^ ^^ ^^ ^^ ^^",
+        "          ... needed 10 more bytes past end of input",
+    ];
+    assert_eq!(report, expected.join("\n"));
+}
+
+#[test]
+fn test_huge_needed() {
+    let buf = [0u8; 20];
+    let mut tranche = BasedBufTranche::new(&buf);
+    tranche.take_front(3).unwrap();
+    let err = tranche.take_front(1 << 24).unwrap_err();
+    let report = Hexdump::new(&buf, &err).to_string();
+    assert_eq!(report.lines().count(), 6);
+    assert!(report.ends_with("... needed 16777199 more bytes past end of input"));
+
+    let err = tranche.take_front(usize::MAX).unwrap_err();
+    assert_eq!(err.span(), Some(3..usize::MAX));
+    let report = Hexdump::new(&buf, &err).with_row_width(7).to_string();
+    assert!(report.len() < 512);
+}
+
+#[test]
+fn test_mismatch() {
+    let buf = b"\x89PNX\r\n";
+    let mut tranche = BasedBufTranche::new(buf);
+    tranche.expect_u8(0x89).unwrap();
+    let err = tranche.expect(b"PNG").unwrap_err();
+    let expected = [
+        "error: mismatch at offset 0x1 (expected [50, 4e, 47], found [50, 4e, 58])",
+        "00000000  89 50 4e 58 0d 0a                                |.PNX..|",
+        "             ^^ ^^ ^^",
+    ];
+    assert_eq!(Hexdump::new(buf, &err).to_string(), expected.join("\n"));
+}
+
+#[test]
+fn test_without_span() {
+    let err = tranche::Error::from(InvalidValueError::new("magic"));
+    assert_eq!(err.span(), None);
+    assert_eq!(
+        Hexdump::new(b"abc", &err).to_string(),
+        "error: invalid magic"
+    );
+
+    let err = BufTranche::new(b"").take_u8().unwrap_err();
+    assert_eq!(
+        Hexdump::new(b"", &err).to_string(),
+        "error: unexpected end (needed 1, got 0)"
+    );
+}
@@ -0,0 +1,68 @@
+use std::fs::{self, File};
+use std::path::PathBuf;
+use tranche::MappedBufTranche;
+
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn new(name: &str, contents: &[u8]) -> Self {
+        let path = std::env::temp_dir().join(format!("tranche-{}-{}", name, std::process::id()));
+        fs::write(&path, contents).unwrap();
+        TempFile(path)
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+#[test]
+fn test_mapped_and_read() {
+    let file = TempFile::new("whole", &[0, 0, 0, 2, 0xde, 0xad, 0xff]);
+    let mapped = unsafe { MappedBufTranche::open(&file.0).unwrap() };
+    let read = MappedBufTranche::read(&file.0).unwrap();
+    assert!(mapped.is_mapped());
+    assert!(!read.is_mapped());
+
+    for tranche in &[mapped, read] {
+        let mut based = tranche.as_based_tranche();
+        let len = based.take_u32_be().unwrap();
+        let body = based.take_front(len as usize).unwrap();
+        assert_eq!(body.as_slice(), &[0xde, 0xad]);
+        assert_eq!(body.offset(), 4);
+        assert_eq!(tranche.absolute_offset(based.offset()), 6);
+        assert_eq!(tranche.len(), 7);
+    }
+}
+
+#[test]
+fn test_ranges() {
+    let contents = (0..=255).collect::<Vec<u8>>();
+    let file = TempFile::new("ranges", &contents);
+    let mut handle = File::open(&file.0).unwrap();
+
+    let mapped = unsafe { MappedBufTranche::map_range(&handle, 100, 10).unwrap() };
+    let read = MappedBufTranche::read_range(&mut handle, 100, 10).unwrap();
+    for tranche in &[mapped, read] {
+        let mut based = tranche.as_based_tranche();
+        based.take_front(3).unwrap();
+        assert_eq!(based.take_u8().unwrap(), 103);
+        assert_eq!(tranche.base_offset(), 100);
+        assert_eq!(tranche.absolute_offset(based.offset()), 104);
+    }
+
+    assert!(unsafe { MappedBufTranche::map_range(&handle, 250, 10) }.is_err());
+    assert!(MappedBufTranche::read_range(&mut handle, 250, 10).is_err());
+    assert!(MappedBufTranche::read_range(&mut handle, u64::MAX, 1).is_err());
+}
+
+#[test]
+fn test_empty() {
+    let file = TempFile::new("empty", &[]);
+    let mapped = unsafe { MappedBufTranche::open(&file.0).unwrap() };
+    assert!(mapped.is_empty());
+    assert!(!mapped.is_mapped());
+    assert!(mapped.as_based_tranche().take_u8().is_err());
+}
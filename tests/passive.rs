@@ -0,0 +1,103 @@
+use tranche::{BasedBufTranche, BufTranche, TakeAsError};
+
+fn words_as_bytes(words: &[u32]) -> &[u8] {
+    unsafe { core::slice::from_raw_parts(words.as_ptr() as *const u8, words.len() * 4) }
+}
+
+#[test]
+fn test_aligned() {
+    let words = [1, 2, 3];
+    let mut tranche = BufTranche::new(words_as_bytes(&words));
+
+    let taken = tranche.take_front_as_aligned::<u32>(2).unwrap();
+    assert_eq!(taken.as_slice(), &[1, 2]);
+    assert_eq!(tranche.len(), 4);
+
+    match tranche.take_front_as_aligned::<u32>(2).unwrap_err() {
+        TakeAsError::UnexpectedEnd(err) => {
+            assert_eq!(err.needed(), 2);
+            assert_eq!(err.len(), 1);
+        }
+        TakeAsError::Misaligned(_) => panic!("unexpected misalignment"),
+    }
+    assert_eq!(tranche.len(), 4);
+}
+
+#[test]
+fn test_misaligned() {
+    let words = [1, 2, 3];
+    let mut tranche = BufTranche::new(words_as_bytes(&words));
+    tranche.take_front(2).unwrap();
+
+    match tranche.take_front_as_aligned::<u32>(1).unwrap_err() {
+        TakeAsError::Misaligned(err) => {
+            assert_eq!(err.align(), 4);
+            assert_eq!(err.misalignment(), 2);
+        }
+        TakeAsError::UnexpectedEnd(_) => panic!("unexpected end"),
+    }
+    assert_eq!(tranche.len(), 10);
+
+    let bytes = words_as_bytes(&words);
+    let [half] = tranche.take_front_as_unaligned::<u32, 1>().unwrap();
+    assert_eq!(
+        half,
+        u32::from_ne_bytes([bytes[2], bytes[3], bytes[4], bytes[5]])
+    );
+    assert_eq!(tranche.len(), 6);
+
+    let err = tranche.take_front_as_unaligned::<u32, 2>().unwrap_err();
+    assert_eq!(err.needed(), 2);
+    assert_eq!(err.len(), 1);
+    assert_eq!(tranche.len(), 6);
+}
+
+#[test]
+fn test_zero_sized() {
+    let mut tranche = BufTranche::new(&[1, 2, 3]);
+    tranche.take_u8().unwrap();
+
+    assert_eq!(tranche.take_front_as::<()>(5).unwrap().len(), 5);
+    assert_eq!(
+        tranche.take_front_as_aligned::<[u64; 0]>(5).unwrap().len(),
+        5
+    );
+    assert_eq!(tranche.take_front_as_unaligned::<(), 5>().unwrap().len(), 5);
+    assert_eq!(tranche.as_slice(), &[2, 3]);
+}
+
+#[test]
+fn test_based_offset() {
+    let words = [1, 2, 3, 4];
+    let bytes = words_as_bytes(&words);
+    let mut tranche = BasedBufTranche::new(&bytes);
+
+    tranche.take_front_as::<[u8; 2]>(1).unwrap();
+    assert_eq!(tranche.offset(), 2);
+
+    tranche.take_front_as_unaligned::<u16, 3>().unwrap();
+    assert_eq!(tranche.offset(), 8);
+
+    tranche.take_front_as_aligned::<u32>(2).unwrap();
+    assert_eq!(tranche.offset(), 16);
+}
+
+#[test]
+fn test_based_misaligned() {
+    let words = [1, 2, 3];
+    let bytes = words_as_bytes(&words);
+    let mut tranche = BasedBufTranche::new(&bytes);
+    tranche.take_front(6).unwrap();
+
+    match tranche.take_front_as_aligned::<u32>(1).unwrap_err() {
+        TakeAsError::Misaligned(err) => {
+            assert_eq!(err.offset(), Some(6));
+            assert_eq!(
+                err.to_string(),
+                "misaligned at offset 0x6 (required alignment 4, off by 2)",
+            );
+        }
+        TakeAsError::UnexpectedEnd(_) => panic!("unexpected end"),
+    }
+    assert_eq!(tranche.offset(), 6);
+}
@@ -1,16 +1,219 @@
 use static_assertions::assert_impl_all;
 use std::error::Error;
-use std::io::{BufRead, Read};
-use tranche::{BasedBufTranche, BufTranche, UnexpectedEndError};
+use std::io::{BufRead, ErrorKind, IoSliceMut, Read, Seek, SeekFrom, Write};
+use tranche::{
+    BasedBufTranche, BufTranche, MisalignedError, ResumableError, SliceWriter, StreamTranche,
+    TakeAsError, UnexpectedEndError, VecWriter,
+};
 
 #[test]
 fn it_compiled() {}
 
+#[allow(clippy::extra_unused_lifetimes)]
 fn _read<'a>() {
     assert_impl_all!(BufTranche<'a>, BufRead, Read);
-    assert_impl_all!(BasedBufTranche<'a>, BufRead, Read);
+    assert_impl_all!(BasedBufTranche<'a>, BufRead, Read, Seek);
+    assert_impl_all!(StreamTranche<&'a [u8]>, BufRead, Read);
+}
+
+#[allow(clippy::extra_unused_lifetimes)]
+fn _write<'a>() {
+    assert_impl_all!(SliceWriter<'a>, Write);
+    assert_impl_all!(VecWriter, Write);
+}
+
+#[test]
+fn test_read_mismatched_sizes() {
+    let mut tranche = BufTranche::new(b"jambon");
+    let mut buf = [0; 4];
+    assert_eq!(tranche.read(&mut buf).unwrap(), 4);
+    assert_eq!(&buf, b"jamb");
+    assert_eq!(tranche.read(&mut buf).unwrap(), 2);
+    assert_eq!(&buf[..2], b"on");
+    assert_eq!(tranche.read(&mut buf).unwrap(), 0);
+
+    let mut tranche = BasedBufTranche::from(BufTranche::new(b"beurre"));
+    let mut buf = [0; 1];
+    assert_eq!(tranche.read(&mut buf).unwrap(), 1);
+    assert_eq!(tranche.read(&mut []).unwrap(), 0);
+    assert_eq!(tranche.offset(), 1);
+}
+
+#[test]
+fn test_read_vectored_and_exact() {
+    let mut tranche = BufTranche::new(b"baguette");
+    let (mut a, mut b, mut c) = ([0; 3], [0; 2], [0; 8]);
+    let mut bufs = [
+        IoSliceMut::new(&mut a),
+        IoSliceMut::new(&mut b),
+        IoSliceMut::new(&mut c),
+    ];
+    assert_eq!(tranche.read_vectored(&mut bufs).unwrap(), 8);
+    assert_eq!((&a, &b, &c[..3]), (b"bag", b"ue", &b"tte"[..]));
+
+    let mut tranche = BufTranche::new(b"pain");
+    let mut buf = [0; 3];
+    tranche.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"pai");
+    let err = tranche.read_exact(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    assert!(tranche.is_empty());
+}
+
+#[test]
+fn test_read_to_end_and_string() {
+    let mut tranche = BufTranche::new(b"jambon");
+    tranche.consume(2);
+    let mut vec = vec![b'>'];
+    assert_eq!(tranche.read_to_end(&mut vec).unwrap(), 4);
+    assert_eq!(vec, b">mbon");
+
+    let mut tranche = BufTranche::new(b"caf\xc3\xa9");
+    let mut string = String::new();
+    assert_eq!(tranche.read_to_string(&mut string).unwrap(), 5);
+    assert_eq!(string, "caf\u{e9}");
+
+    let mut tranche = BufTranche::new(b"caf\xc3");
+    let err = tranche.read_to_string(&mut string).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert_eq!(tranche.len(), 4);
+}
+
+#[test]
+fn test_buf_read() {
+    let mut tranche = BasedBufTranche::from(BufTranche::new(b"un\ndeux\ntrois"));
+    let mut line = String::new();
+    assert_eq!(tranche.read_line(&mut line).unwrap(), 3);
+    assert_eq!(line, "un\n");
+    let mut vec = vec![];
+    assert_eq!(tranche.read_until(b'x', &mut vec).unwrap(), 4);
+    assert_eq!(vec, b"deux");
+    assert_eq!(
+        tranche.lines().map(Result::unwrap).collect::<Vec<_>>(),
+        ["", "trois"],
+    );
+
+    let mut tranche = BufTranche::new(b"caf\xc3\nthe\n");
+    line.clear();
+    let err = tranche.read_line(&mut line).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert_eq!(line, "");
+    assert_eq!(tranche.read_line(&mut line).unwrap(), 4);
+    assert_eq!(line, "the\n");
+
+    let mut tranche = BufTranche::new(b"abc");
+    tranche.consume(10);
+    assert!(tranche.is_empty());
+    assert_eq!(tranche.read_until(b'\n', &mut vec).unwrap(), 0);
+}
+
+#[test]
+fn test_seek() {
+    let mut tranche = BasedBufTranche::from(BufTranche::new(b"jambon-beurre"));
+    assert_eq!(tranche.seek(SeekFrom::End(-6)).unwrap(), 7);
+    let mut buf = [0; 6];
+    tranche.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"beurre");
+    assert_eq!(tranche.seek(SeekFrom::Current(-13)).unwrap(), 0);
+    assert_eq!(tranche.seek(SeekFrom::Start(3)).unwrap(), 3);
+    assert_eq!(tranche.stream_position().unwrap(), 3);
+    assert_eq!(tranche.seek(SeekFrom::End(0)).unwrap(), 13);
+    assert!(tranche.is_empty());
+
+    for &pos in &[
+        SeekFrom::Start(14),
+        SeekFrom::Current(1),
+        SeekFrom::End(-14),
+    ] {
+        let err = tranche.seek(pos).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+    assert_eq!(tranche.offset(), 13);
+
+    Seek::rewind(&mut tranche).unwrap();
+    assert_eq!(tranche.offset(), 0);
+}
+
+#[test]
+fn test_error_into_io_error() {
+    let err = std::io::Error::from(tranche::Error::from(
+        BufTranche::new(&[]).take_u8().unwrap_err(),
+    ));
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    let err = std::io::Error::from(tranche::Error::from(
+        BufTranche::new(&[1]).finish().unwrap_err(),
+    ));
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    let err = std::io::Error::from(BufTranche::new(b"GIF87a").expect(b"GIF89a").unwrap_err());
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert_eq!(
+        err.to_string(),
+        "mismatch (expected [47, 49, 46, 38, 39, 61], found [47, 49, 46, 38, 37, 61])"
+    );
+}
+
+#[test]
+fn test_slice_writer_write() {
+    let mut buf = [0; 4];
+    let mut writer = SliceWriter::new(&mut buf);
+    assert_eq!(writer.write(b"abc").unwrap(), 3);
+    assert_eq!(writer.write(b"def").unwrap(), 1);
+    assert_eq!(writer.write(b"g").unwrap(), 0);
+    assert!(writer.write_all(b"h").is_err());
+    assert_eq!(writer.written(), b"abcd");
 }
 
 fn _error() {
     assert_impl_all!(UnexpectedEndError, Error);
+    assert_impl_all!(MisalignedError, Error);
+    assert_impl_all!(TakeAsError, Error);
+    assert_impl_all!(ResumableError<UnexpectedEndError>, Error);
+}
+
+struct Trickle<'a>(&'a [u8]);
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.0.is_empty() || buf.is_empty() {
+            return Ok(0);
+        }
+        buf[0] = self.0[0];
+        self.0 = &self.0[1..];
+        Ok(1)
+    }
+}
+
+#[test]
+fn test_stream_refills() {
+    let input = (0..=255).cycle().take(1000).collect::<Vec<u8>>();
+    let mut stream = StreamTranche::with_max_window(Trickle(&input), 16);
+
+    assert_eq!(stream.take_u16_be().unwrap(), 0x0001);
+    assert_eq!(stream.take_front(16).unwrap().as_slice(), &input[2..18]);
+    assert_eq!(stream.offset(), 18);
+
+    let err = stream.take_front(17).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+
+    stream.skip(900).unwrap();
+    assert_eq!(stream.offset(), 918);
+    assert_eq!(stream.take_u8().unwrap(), input[918]);
+
+    let mut rest = Vec::new();
+    stream.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, &input[919..]);
+    assert_eq!(stream.offset(), 1000);
+}
+
+#[test]
+fn test_stream_unexpected_end() {
+    let mut stream = StreamTranche::new(Trickle(&[1, 2, 3]));
+    let err = stream.take_u32_le().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    assert_eq!(stream.offset(), 0);
+    assert_eq!(stream.buffered().as_slice(), &[1, 2, 3]);
+
+    assert_eq!(stream.take_u16_le().unwrap(), 0x0201);
+    assert!(stream.skip(2).is_err());
+    assert_eq!(stream.offset(), 3);
 }
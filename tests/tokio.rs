@@ -0,0 +1,81 @@
+use bytes::BytesMut;
+use futures::StreamExt;
+use static_assertions::assert_impl_all;
+use std::io;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio_util::codec::{Decoder, FramedRead};
+use tranche::{BasedBufTranche, BufTranche, ResumableError, TrancheDecoder};
+
+#[allow(clippy::extra_unused_lifetimes)]
+fn _async<'a>() {
+    assert_impl_all!(BufTranche<'a>, AsyncBufRead, AsyncRead);
+    assert_impl_all!(BasedBufTranche<'a>, AsyncBufRead, AsyncRead);
+}
+
+#[tokio::test]
+async fn test_async_read() {
+    let mut tranche = BufTranche::new(b"jambon\nbeurre");
+    let mut buf = [0; 4];
+    assert_eq!(tranche.read(&mut buf).await.unwrap(), 4);
+    assert_eq!(&buf, b"jamb");
+    let mut line = String::new();
+    tranche.read_line(&mut line).await.unwrap();
+    assert_eq!(line, "on\n");
+    assert_eq!(tranche.fill_buf().await.unwrap(), b"beurre");
+}
+
+fn frame(t: &mut BufTranche) -> Result<(u16, Vec<u8>), ResumableError<io::Error>> {
+    let tag = t.take_u16_be()?;
+    let len = t.take_u8()?;
+    if len > 8 {
+        return Err(ResumableError::Malformed(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "frame too long",
+        )));
+    }
+    Ok((tag, t.take_front(len as usize)?.as_slice().to_vec()))
+}
+
+#[tokio::test]
+async fn test_decoder_over_duplex() {
+    let (mut client, server) = tokio::io::duplex(4);
+    let writer = tokio::spawn(async move {
+        let input = [
+            0, 1, 3, b'a', b'b', b'c', 0, 2, 0, 0xca, 0xfe, 1, b'!', 0xff,
+        ];
+        for byte in &input {
+            client.write_all(&[*byte]).await.unwrap();
+        }
+    });
+
+    let mut frames = FramedRead::new(server, TrancheDecoder::new(frame));
+    assert_eq!(frames.next().await.unwrap().unwrap(), (1, b"abc".to_vec()));
+    assert_eq!(frames.next().await.unwrap().unwrap(), (2, vec![]));
+    assert_eq!(
+        frames.next().await.unwrap().unwrap(),
+        (0xcafe, b"!".to_vec())
+    );
+    writer.await.unwrap();
+    let err = frames.next().await.unwrap().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Other);
+    assert_eq!(frames.read_buffer().as_ref(), &[0xff]);
+}
+
+#[tokio::test]
+async fn test_decoder_malformed() {
+    let input: &[u8] = &[0, 1, 9];
+    let mut frames = FramedRead::new(input, TrancheDecoder::new(frame));
+    let err = frames.next().await.unwrap().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+#[should_panic(expected = "tranche was replaced by an unrelated one")]
+fn test_decoder_foreign_tranche() {
+    static FOREIGN: [u8; 8] = [0; 8];
+    let mut decoder = TrancheDecoder::new(|t: &mut BufTranche| {
+        *t = BufTranche::new(&FOREIGN);
+        t.take_u8().map_err(ResumableError::<io::Error>::from)
+    });
+    let _ = decoder.decode(&mut BytesMut::from(&[0u8; 4][..]));
+}
@@ -0,0 +1,50 @@
+use core::fmt::Write;
+use tranche::{BufTranche, SliceWriter};
+
+#[test]
+fn test_round_trip() {
+    let mut buf = [0; 32];
+    let mut writer = SliceWriter::new(&mut buf);
+    writer.put_u8(1).unwrap();
+    writer.put_i16_le(-2).unwrap();
+    writer.put_u32_be(0xdead_beef).unwrap();
+    writer.put_i64_ne(-4).unwrap();
+    writer.put_u128_le(5).unwrap();
+    assert_eq!(writer.offset(), 31);
+    assert_eq!(writer.remaining(), 1);
+
+    let mut tranche = BufTranche::new(writer.written());
+    assert_eq!(tranche.take_u8().unwrap(), 1);
+    assert_eq!(tranche.take_i16_le().unwrap(), -2);
+    assert_eq!(tranche.take_u32_be().unwrap(), 0xdead_beef);
+    assert_eq!(tranche.take_i64_ne().unwrap(), -4);
+    assert_eq!(tranche.take_u128_le().unwrap(), 5);
+    assert!(tranche.is_empty());
+}
+
+#[test]
+fn test_full() {
+    let mut buf = [0; 3];
+    let mut writer = SliceWriter::new(&mut buf);
+    writer.put_u8(1).unwrap();
+
+    let err = writer.put_u32_le(2).unwrap_err();
+    assert_eq!(err.needed(), 4);
+    assert_eq!(err.len(), 2);
+    assert_eq!(writer.written(), &[1]);
+
+    writer.put_u16_be(0x0203).unwrap();
+    assert!(writer.is_full());
+    assert!(writer.put_u8(4).is_err());
+    assert_eq!(writer.into_written(), &[1, 2, 3]);
+}
+
+#[test]
+fn test_fmt_write() {
+    let mut buf = [0; 8];
+    let mut writer = SliceWriter::new(&mut buf);
+    write!(writer, "{}-{}", 12, 34).unwrap();
+    assert_eq!(writer.written(), b"12-34");
+    assert!(write!(writer, "{}", 5678).is_err());
+    assert_eq!(writer.written(), b"12-34");
+}
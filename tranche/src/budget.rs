@@ -0,0 +1,213 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+
+use crate::error::LimitExceededError;
+
+const DEFAULT_MAX_DEPTH: usize = 64;
+
+/// A tranche along with the resources a parser may still use on its behalf.
+///
+/// Untrusted input can declare huge counts and lengths, or nest structures
+/// deeply enough to overflow the stack. Parsers report the element counts
+/// and allocation sizes they are about to act upon with `count` and
+/// `reserve`, and wrap nested structures with `enter` and `exit`, all of
+/// which fail once the corresponding limit is exceeded. Counts and
+/// allocation sizes add up over the whole parse.
+///
+/// By default, only the nesting depth is limited, to 64. The tranche is
+/// reachable through `Deref` and `DerefMut`.
+///
+/// # Examples
+///
+/// ```
+/// # use tranche::{Budget, BufTranche, Error};
+/// fn list(t: &mut Budget<BufTranche>) -> Result<Vec<u8>, Error> {
+///     let len = t.take_u8()? as usize;
+///     let mut items = Vec::with_capacity(t.count(len)?);
+///     for _ in 0..len {
+///         items.push(match t.take_u8()? {
+///             0xff => t.nested(|t| Ok::<_, Error>(list(t)?.len() as u8))?,
+///             item => item,
+///         });
+///     }
+///     Ok(items)
+/// }
+///
+/// let mut t = Budget::new(BufTranche::new(&[2, 1, 0xff, 1, 3])).with_max_elements(3);
+/// assert_eq!(list(&mut t).unwrap(), [1, 1]);
+///
+/// let mut t = Budget::new(BufTranche::new(&[2, 0xff, 2])).with_max_elements(3);
+/// assert_eq!(list(&mut t).unwrap_err().to_string(), "element count limit of 3 exceeded");
+///
+/// let mut t = Budget::new(BufTranche::new(&[1, 0xff, 1, 0xff, 0])).with_max_depth(1);
+/// assert_eq!(list(&mut t).unwrap_err().to_string(), "nesting depth limit of 1 exceeded");
+/// ```
+#[derive(Clone, Debug)]
+pub struct Budget<T> {
+    tranche: T,
+    elements: usize,
+    max_elements: usize,
+    allocated: usize,
+    max_alloc: usize,
+    depth: usize,
+    max_depth: usize,
+}
+
+/// A limit enforced by [`Budget<T>`](struct.Budget.html).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Limit {
+    /// The total number of elements declared with `count`.
+    Elements,
+    /// The total number of bytes declared with `reserve`.
+    Allocation,
+    /// The nesting depth tracked with `enter` and `exit`.
+    Depth,
+}
+
+impl<T> Budget<T> {
+    /// Creates a new budget around the given tranche, with the default
+    /// limits.
+    pub fn new(tranche: T) -> Self {
+        Self {
+            tranche,
+            elements: 0,
+            max_elements: usize::MAX,
+            allocated: 0,
+            max_alloc: usize::MAX,
+            depth: 0,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    /// Sets the maximum total number of elements declared with `count`.
+    pub fn with_max_elements(self, max_elements: usize) -> Self {
+        Self {
+            max_elements,
+            ..self
+        }
+    }
+
+    /// Sets the maximum total number of bytes declared with `reserve`.
+    pub fn with_max_alloc(self, max_alloc: usize) -> Self {
+        Self { max_alloc, ..self }
+    }
+
+    /// Sets the maximum nesting depth tracked with `enter` and `exit`.
+    pub fn with_max_depth(self, max_depth: usize) -> Self {
+        Self { max_depth, ..self }
+    }
+
+    /// Declares that `n` elements are about to be parsed.
+    ///
+    /// Returns `n`, or `Err(_)` if this would exceed the maximum total
+    /// number of elements, in which case nothing is counted.
+    pub fn count(&mut self, n: usize) -> Result<usize, LimitExceededError> {
+        self.elements = charge(self.elements, n, self.max_elements, Limit::Elements)?;
+        Ok(n)
+    }
+
+    /// Declares that `n` bytes are about to be allocated.
+    ///
+    /// Returns `n`, or `Err(_)` if this would exceed the maximum total
+    /// number of bytes, in which case nothing is counted.
+    pub fn reserve(&mut self, n: usize) -> Result<usize, LimitExceededError> {
+        self.allocated = charge(self.allocated, n, self.max_alloc, Limit::Allocation)?;
+        Ok(n)
+    }
+
+    /// Enters a nested structure.
+    ///
+    /// Returns `Err(_)` if this would exceed the maximum nesting depth, in
+    /// which case the depth is left untouched and `exit` must not be called.
+    pub fn enter(&mut self) -> Result<(), LimitExceededError> {
+        self.depth = charge(self.depth, 1, self.max_depth, Limit::Depth)?;
+        Ok(())
+    }
+
+    /// Exits a nested structure entered with `enter`.
+    ///
+    /// This does nothing if no nested structure was entered.
+    pub fn exit(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+    }
+
+    /// Runs a parser on a nested structure, calling `enter` before it and
+    /// `exit` after it, whether it succeeded or not.
+    pub fn nested<R, E>(&mut self, f: impl FnOnce(&mut Self) -> Result<R, E>) -> Result<R, E>
+    where
+        E: From<LimitExceededError>,
+    {
+        self.enter()?;
+        let res = f(self);
+        self.exit();
+        res
+    }
+
+    /// Returns the total number of elements declared with `count` so far.
+    pub fn elements(&self) -> usize {
+        self.elements
+    }
+
+    /// Returns the total number of bytes declared with `reserve` so far.
+    pub fn allocated(&self) -> usize {
+        self.allocated
+    }
+
+    /// Returns the current nesting depth.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns a reference to the underlying tranche.
+    pub fn get_ref(&self) -> &T {
+        &self.tranche
+    }
+
+    /// Returns a mutable reference to the underlying tranche.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.tranche
+    }
+
+    /// Consumes the budget, returning the underlying tranche.
+    pub fn into_inner(self) -> T {
+        self.tranche
+    }
+}
+
+impl<T> Deref for Budget<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.tranche
+    }
+}
+
+impl<T> DerefMut for Budget<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.tranche
+    }
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(match self {
+            Limit::Elements => "element count",
+            Limit::Allocation => "allocation",
+            Limit::Depth => "nesting depth",
+        })
+    }
+}
+
+fn charge(used: usize, n: usize, max: usize, limit: Limit) -> Result<usize, LimitExceededError> {
+    match used.checked_add(n) {
+        Some(used) if used <= max => Ok(used),
+        _ => Err(LimitExceededError::new(limit, max)),
+    }
+}
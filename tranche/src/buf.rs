@@ -4,36 +4,75 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use core::convert::TryFrom;
 use core::mem;
 
-use crate::core::{BasedBufTranche, BufTranche, UnexpectedEndError};
+use crate::core::{BasedBufTranche, BufTranche, OverflowError, UnexpectedEndError};
+use crate::error::{ExpectError, MismatchError, TakeLenError};
 
 macro_rules! call_for_each_taker {
     ($mac:ident) => {
         call_for_each_taker! {
             $mac
 
-            u16 take_u16_ne take_u16_le take_u16_be
-            i16 take_i16_ne take_i16_le take_i16_be
+            u16 take_u16_ne take_u16_le take_u16_be put_u16_ne put_u16_le put_u16_be
+                expect_u16_ne expect_u16_le expect_u16_be
+            i16 take_i16_ne take_i16_le take_i16_be put_i16_ne put_i16_le put_i16_be
+                expect_i16_ne expect_i16_le expect_i16_be
 
-            u32 take_u32_ne take_u32_le take_u32_be
-            i32 take_i32_ne take_i32_le take_i32_be
+            u32 take_u32_ne take_u32_le take_u32_be put_u32_ne put_u32_le put_u32_be
+                expect_u32_ne expect_u32_le expect_u32_be
+            i32 take_i32_ne take_i32_le take_i32_be put_i32_ne put_i32_le put_i32_be
+                expect_i32_ne expect_i32_le expect_i32_be
 
-            u64 take_u64_ne take_u64_le take_u64_be
-            i64 take_i64_ne take_i64_le take_i64_be
+            u64 take_u64_ne take_u64_le take_u64_be put_u64_ne put_u64_le put_u64_be
+                expect_u64_ne expect_u64_le expect_u64_be
+            i64 take_i64_ne take_i64_le take_i64_be put_i64_ne put_i64_le put_i64_be
+                expect_i64_ne expect_i64_le expect_i64_be
 
-            u128 take_u128_ne take_u128_le take_u128_be
-            i128 take_i128_ne take_i128_le take_i128_be
+            u128 take_u128_ne take_u128_le take_u128_be put_u128_ne put_u128_le put_u128_be
+                expect_u128_ne expect_u128_le expect_u128_be
+            i128 take_i128_ne take_i128_le take_i128_be put_i128_ne put_i128_le put_i128_be
+                expect_i128_ne expect_i128_le expect_i128_be
 
-            usize take_usize_ne take_usize_le take_usize_be
-            isize take_isize_ne take_isize_le take_isize_be
+            usize take_usize_ne take_usize_le take_usize_be put_usize_ne put_usize_le put_usize_be
+                expect_usize_ne expect_usize_le expect_usize_be
+            isize take_isize_ne take_isize_le take_isize_be put_isize_ne put_isize_le put_isize_be
+                expect_isize_ne expect_isize_le expect_isize_be
         }
     };
-    ($mac:ident $($ty:ident $ne:ident $le:ident $be:ident)+) => {
+    ($mac:ident $(
+        $ty:ident $ne:ident $le:ident $be:ident $put_ne:ident $put_le:ident $put_be:ident
+        $expect_ne:ident $expect_le:ident $expect_be:ident
+    )+) => {
         $(
-            $mac!($ty "native" $ne from_ne_bytes);
-            $mac!($ty "little" $le from_le_bytes);
-            $mac!($ty "big" $be from_be_bytes);
+            $mac!($ty "native" $ne from_ne_bytes $put_ne to_ne_bytes $expect_ne);
+            $mac!($ty "little" $le from_le_bytes $put_le to_le_bytes $expect_le);
+            $mac!($ty "big" $be from_be_bytes $put_be to_be_bytes $expect_be);
+        )+
+    };
+}
+
+macro_rules! call_for_each_len_taker {
+    ($mac:ident) => {
+        call_for_each_len_taker! {
+            $mac
+
+            u16 take_u16_ne take_u16_le take_u16_be
+                take_u16_ne_as_len take_u16_le_as_len take_u16_be_as_len
+            u32 take_u32_ne take_u32_le take_u32_be
+                take_u32_ne_as_len take_u32_le_as_len take_u32_be_as_len
+            u64 take_u64_ne take_u64_le take_u64_be
+                take_u64_ne_as_len take_u64_le_as_len take_u64_be_as_len
+        }
+    };
+    ($mac:ident $(
+        $ty:ident $ne:ident $le:ident $be:ident $len_ne:ident $len_le:ident $len_be:ident
+    )+) => {
+        $(
+            $mac!($ty "native" $ne $len_ne);
+            $mac!($ty "little" $le $len_le);
+            $mac!($ty "big" $be $len_be);
         )+
     };
 }
@@ -45,7 +84,7 @@ macro_rules! taker_with_computed_doc {
 }
 
 macro_rules! tranche_taker {
-    ($ty:ident $endian:tt $take:ident $from:ident) => {
+    ($ty:ident $endian:tt $take:ident $from:ident $put:ident $to:ident $expect:ident) => {
         taker_with_computed_doc! {
             /// Returns a
             #[doc = concat!("`", stringify!($ty), "`")]
@@ -63,6 +102,53 @@ macro_rules! tranche_taker {
                 Ok($ty::$from(unsafe { *(ptr as *const [u8; SIZE]) }))
             }
         }
+
+        taker_with_computed_doc! {
+            /// Takes a
+            #[doc = concat!("`", stringify!($ty), "`")]
+            /// equal to `expected` out of the tranche in
+            #[doc = $endian]
+            /// endian order.
+            ///
+            /// Returns `Err(_)` with the value found instead if it is not
+            /// equal to `expected`, or if `self` is not long enough, in which
+            /// case the tranche is left untouched.
+            #[inline]
+            pub fn $expect(&mut self, expected: $ty) -> Result<(), ExpectError<$ty>> {
+                let mut rest = self.clone();
+                let found = rest.$take()?;
+                if found != expected {
+                    let len = mem::size_of::<$ty>();
+                    return Err(MismatchError::new(expected, found, len).into());
+                }
+                *self = rest;
+                Ok(())
+            }
+        }
+    };
+}
+
+macro_rules! tranche_len_taker {
+    ($ty:ident $endian:tt $take:ident $take_len:ident) => {
+        taker_with_computed_doc! {
+            /// Returns a length by taking a
+            #[doc = concat!("`", stringify!($ty), "`")]
+            /// out of the tranche in
+            #[doc = $endian]
+            /// endian order and converting it to a `usize`.
+            ///
+            /// Returns `Err(_)` if `self` is not long enough or if the length
+            /// does not fit in a `usize`, in which case the tranche is left
+            /// untouched.
+            #[inline]
+            pub fn $take_len(&mut self) -> Result<usize, TakeLenError> {
+                let mut rest = self.clone();
+                let len = rest.$take()?;
+                let len = usize::try_from(len).map_err(|_| OverflowError::new())?;
+                *self = rest;
+                Ok(len)
+            }
+        }
     };
 }
 
@@ -81,11 +167,57 @@ impl BufTranche<'_> {
         Ok(self.take_u8()? as i8)
     }
 
+    /// Takes the first `u8` out of the tranche if it is equal to `expected`.
+    ///
+    /// Returns `Err(_)` with the value found instead if it is not equal to
+    /// `expected`, or if `self` is not long enough, in which case the tranche
+    /// is left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tranche::BufTranche;
+    /// let mut tranche = BufTranche::new(&[0x89, b'P', b'N', b'G', 0x0d, 0x0a]);
+    /// assert!(tranche.expect_u8(0x89).is_ok());
+    /// assert!(tranche.expect_u32_be(0x504E470a).is_err());
+    /// assert!(tranche.expect_u16_le(0x4e50).is_ok());
+    /// assert_eq!(tranche.as_slice(), b"G\r\n");
+    /// ```
+    pub fn expect_u8(&mut self, expected: u8) -> Result<(), ExpectError<u8>> {
+        let found = *self
+            .as_slice()
+            .first()
+            .ok_or(UnexpectedEndError::new(1, 0))?;
+        if found != expected {
+            return Err(MismatchError::new(expected, found, 1).into());
+        }
+        self.take_first()?;
+        Ok(())
+    }
+
+    /// Takes the first `i8` out of the tranche if it is equal to `expected`.
+    ///
+    /// Returns `Err(_)` with the value found instead if it is not equal to
+    /// `expected`, or if `self` is not long enough, in which case the tranche
+    /// is left untouched.
+    pub fn expect_i8(&mut self, expected: i8) -> Result<(), ExpectError<i8>> {
+        let found = *self
+            .as_slice()
+            .first()
+            .ok_or(UnexpectedEndError::new(1, 0))? as i8;
+        if found != expected {
+            return Err(MismatchError::new(expected, found, 1).into());
+        }
+        self.take_first()?;
+        Ok(())
+    }
+
     call_for_each_taker!(tranche_taker);
+    call_for_each_len_taker!(tranche_len_taker);
 }
 
 macro_rules! based_tranche_taker {
-    ($ty:ident $endian:tt $take:ident $from:ident) => {
+    ($ty:ident $endian:tt $take:ident $from:ident $put:ident $to:ident $expect:ident) => {
         taker_with_computed_doc! {
             /// Returns a
             #[doc = concat!("`", stringify!($ty), "`")]
@@ -97,10 +229,54 @@ macro_rules! based_tranche_taker {
             ///
             /// The internal offset is incremented accordingly.
             ///
-            /// Returns `Err(_)` if `self` is not long enough.
+            /// Returns `Err(_)` carrying the current offset if `self` is not long
+            /// enough.
             #[inline]
             pub fn $take(&mut self) -> Result<$ty, UnexpectedEndError> {
-                self.inner.$take()
+                let offset = self.offset();
+                self.inner.$take().map_err(|err| err.at(offset))
+            }
+        }
+
+        taker_with_computed_doc! {
+            /// Takes a
+            #[doc = concat!("`", stringify!($ty), "`")]
+            /// equal to `expected` out of the tranche in
+            #[doc = $endian]
+            /// endian order.
+            ///
+            /// The internal offset is incremented accordingly.
+            ///
+            /// Returns `Err(_)` carrying the current offset if it is not
+            /// equal to `expected` or if `self` is not long enough.
+            #[inline]
+            pub fn $expect(&mut self, expected: $ty) -> Result<(), ExpectError<$ty>> {
+                let offset = self.offset();
+                self.inner.$expect(expected).map_err(|err| err.at(offset))
+            }
+        }
+    };
+}
+
+macro_rules! based_tranche_len_taker {
+    ($ty:ident $endian:tt $take:ident $take_len:ident) => {
+        taker_with_computed_doc! {
+            /// Returns a length by taking a
+            #[doc = concat!("`", stringify!($ty), "`")]
+            /// out of the tranche in
+            #[doc = $endian]
+            /// endian order and converting it to a `usize`.
+            ///
+            /// The internal offset is incremented accordingly.
+            ///
+            /// Returns `Err(_)` carrying the current offset if `self` is not
+            /// long enough or if the length does not fit in a `usize`.
+            #[inline]
+            pub fn $take_len(&mut self) -> Result<usize, TakeLenError> {
+                let offset = self.offset();
+                self.inner
+                    .$take_len()
+                    .map_err(|err| err.at(offset, mem::size_of::<$ty>()))
             }
         }
     };
@@ -111,19 +287,45 @@ impl BasedBufTranche<'_> {
     ///
     /// The internal offset is incremented accordingly.
     ///
-    /// Returns `Err(_)` if `self` is not long enough.
+    /// Returns `Err(_)` carrying the current offset if `self` is not long
+    /// enough.
     pub fn take_u8(&mut self) -> Result<u8, UnexpectedEndError> {
-        self.inner.take_u8()
+        let offset = self.offset();
+        self.inner.take_u8().map_err(|err| err.at(offset))
     }
 
     /// Takes the first `i8` out of the tranche.
     ///
     /// The internal offset is incremented accordingly.
     ///
-    /// Returns `Err(_)` if `self` is not long enough.
+    /// Returns `Err(_)` carrying the current offset if `self` is not long
+    /// enough.
     pub fn take_i8(&mut self) -> Result<i8, UnexpectedEndError> {
-        self.inner.take_i8()
+        Ok(self.take_u8()? as i8)
+    }
+
+    /// Takes the first `u8` out of the tranche if it is equal to `expected`.
+    ///
+    /// The internal offset is incremented accordingly.
+    ///
+    /// Returns `Err(_)` carrying the current offset if it is not equal to
+    /// `expected` or if `self` is not long enough.
+    pub fn expect_u8(&mut self, expected: u8) -> Result<(), ExpectError<u8>> {
+        let offset = self.offset();
+        self.inner.expect_u8(expected).map_err(|err| err.at(offset))
+    }
+
+    /// Takes the first `i8` out of the tranche if it is equal to `expected`.
+    ///
+    /// The internal offset is incremented accordingly.
+    ///
+    /// Returns `Err(_)` carrying the current offset if it is not equal to
+    /// `expected` or if `self` is not long enough.
+    pub fn expect_i8(&mut self, expected: i8) -> Result<(), ExpectError<i8>> {
+        let offset = self.offset();
+        self.inner.expect_i8(expected).map_err(|err| err.at(offset))
     }
 
     call_for_each_taker!(based_tranche_taker);
+    call_for_each_len_taker!(based_tranche_len_taker);
 }
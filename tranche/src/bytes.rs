@@ -0,0 +1,62 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use bytes::{Buf, Bytes};
+
+use crate::core::{BasedBufTranche, BufTranche};
+
+impl Buf for BufTranche<'_> {
+    fn remaining(&self) -> usize {
+        self.len()
+    }
+
+    fn chunk(&self) -> &[u8] {
+        self.as_slice()
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        if let Err(err) = self.take_front(cnt) {
+            panic!("{}", err);
+        }
+    }
+
+    fn copy_to_bytes(&mut self, len: usize) -> Bytes {
+        match self.take_front(len) {
+            Ok(front) => Bytes::copy_from_slice(front.as_slice()),
+            Err(err) => panic!("{}", err),
+        }
+    }
+}
+
+impl Buf for BasedBufTranche<'_> {
+    fn remaining(&self) -> usize {
+        self.len()
+    }
+
+    fn chunk(&self) -> &[u8] {
+        self.as_slice()
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        self.inner.advance(cnt)
+    }
+
+    fn copy_to_bytes(&mut self, len: usize) -> Bytes {
+        self.inner.copy_to_bytes(len)
+    }
+}
+
+impl<'a> From<&'a Bytes> for BufTranche<'a> {
+    fn from(bytes: &'a Bytes) -> Self {
+        Self::new(bytes)
+    }
+}
+
+impl<'a> From<&'a Bytes> for BasedBufTranche<'a> {
+    fn from(bytes: &'a Bytes) -> Self {
+        Self::new(bytes)
+    }
+}
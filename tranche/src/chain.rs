@@ -0,0 +1,292 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use core::cmp;
+use core::fmt;
+use core::iter;
+use core::mem;
+
+use crate::core::{BufTranche, UnexpectedEndError};
+
+macro_rules! chained_tranche_taker {
+    ($ty:ident $endian:tt $take:ident $from:ident $put:ident $to:ident $expect:ident) => {
+        taker_with_computed_doc! {
+            /// Returns a
+            #[doc = concat!("`", stringify!($ty), "`")]
+            /// by taking the first
+            #[doc = concat!("`mem::size_of::<", stringify!($ty), ">()`")]
+            /// bytes out of the tranche in
+            #[doc = $endian]
+            /// endian order, even if they span several segments.
+            ///
+            /// The internal offset is incremented accordingly.
+            ///
+            /// Returns `Err(_)` if `self` is not long enough.
+            #[inline]
+            pub fn $take(&mut self) -> Result<$ty, UnexpectedEndError> {
+                if let Ok(value) = self.front.clone().$take() {
+                    self.advance(mem::size_of::<$ty>());
+                    return Ok(value);
+                }
+                let mut bytes = [0; mem::size_of::<$ty>()];
+                self.take_into(&mut bytes)?;
+                Ok($ty::$from(bytes))
+            }
+        }
+    };
+}
+
+/// A tranche of bytes spread over several segments.
+///
+/// Chained tranches are to scatter/gather lists what buffer tranches are to
+/// byte slices. Integers spanning segment boundaries are assembled
+/// transparently, and the number of bytes taken from the front of the
+/// original chained tranche is tracked like with `BasedTranche::offset`.
+///
+/// # Examples
+///
+/// ```
+/// # use tranche::ChainedBufTranche;
+/// let segments: [&[u8]; 3] = [&[0, 0, 1], &[], &[2, 0xff]];
+/// let mut tranche = ChainedBufTranche::new(&segments);
+/// assert_eq!(tranche.take_u32_be().unwrap(), 0x0102);
+/// assert_eq!(tranche.offset(), 4);
+/// assert_eq!(tranche.take_u8().unwrap(), 0xff);
+/// assert!(tranche.is_empty());
+/// ```
+#[derive(Clone, Default)]
+pub struct ChainedBufTranche<'a> {
+    front: BufTranche<'a>,
+    rest: &'a [&'a [u8]],
+    len: usize,
+    offset: usize,
+}
+
+/// The front of a chained tranche, as returned by
+/// `ChainedBufTranche::take_front`.
+#[derive(Clone, Debug)]
+pub enum ChainedFront<'a> {
+    /// The bytes taken were all in the same segment.
+    Contiguous(BufTranche<'a>),
+    /// The bytes taken span several segments.
+    Chained(ChainedBufTranche<'a>),
+}
+
+impl<'a> ChainedBufTranche<'a> {
+    /// Creates a new chained tranche over the given segments.
+    pub fn new(segments: &'a [&'a [u8]]) -> Self {
+        let len = segments.iter().map(|segment| segment.len()).sum();
+        let mut tranche = Self {
+            front: Default::default(),
+            rest: segments,
+            len,
+            offset: 0,
+        };
+        tranche.skip_empty_segments();
+        tranche
+    }
+
+    /// Returns the number of bytes in the tranche.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the tranche has a length of 0.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the starting offset of this chained tranche.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the bytes at the front of the tranche which are contiguous
+    /// in memory.
+    ///
+    /// This is empty only if the tranche itself is empty.
+    pub fn chunk(&self) -> &'a [u8] {
+        self.front.as_slice()
+    }
+
+    /// Returns an iterator over the non-empty contiguous chunks of bytes in
+    /// the tranche.
+    pub fn chunks(&self) -> impl Iterator<Item = &'a [u8]> + 'a {
+        let mut remaining = self.len - self.front.len();
+        let rest = self.rest.iter().map(move |segment| {
+            let chunk = &segment[..cmp::min(segment.len(), remaining)];
+            remaining -= chunk.len();
+            chunk
+        });
+        iter::once(self.chunk())
+            .chain(rest)
+            .filter(|chunk| !chunk.is_empty())
+    }
+
+    /// Takes the first `u8` out of the tranche.
+    ///
+    /// The internal offset is incremented accordingly.
+    ///
+    /// Returns `Err(_)` if `self` is not long enough.
+    pub fn take_u8(&mut self) -> Result<u8, UnexpectedEndError> {
+        let byte = *self.front.clone().take_first()?;
+        self.advance(1);
+        Ok(byte)
+    }
+
+    /// Takes the first `i8` out of the tranche.
+    ///
+    /// The internal offset is incremented accordingly.
+    ///
+    /// Returns `Err(_)` if `self` is not long enough.
+    pub fn take_i8(&mut self) -> Result<i8, UnexpectedEndError> {
+        Ok(self.take_u8()? as i8)
+    }
+
+    call_for_each_taker!(chained_tranche_taker);
+
+    /// Takes the first `n` bytes out of the tranche.
+    ///
+    /// Returns a contiguous tranche if the `n` bytes are all in the same
+    /// segment, or a chained tranche otherwise, or `Err(_)` if `self` is not
+    /// long enough. The internal offset is incremented accordingly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tranche::{ChainedBufTranche, ChainedFront};
+    /// let segments: [&[u8]; 2] = [b"jambon", b"beurre"];
+    /// let mut tranche = ChainedBufTranche::new(&segments);
+    ///
+    /// match tranche.take_front(3).unwrap() {
+    ///     ChainedFront::Contiguous(jam) => assert_eq!(jam.as_slice(), b"jam"),
+    ///     ChainedFront::Chained(_) => unreachable!(),
+    /// }
+    /// match tranche.take_front(5).unwrap() {
+    ///     ChainedFront::Chained(bonbe) => {
+    ///         assert_eq!(bonbe.offset(), 3);
+    ///         assert_eq!(bonbe.chunks().collect::<Vec<_>>(), [&b"bon"[..], b"be"]);
+    ///     }
+    ///     ChainedFront::Contiguous(_) => unreachable!(),
+    /// }
+    ///
+    /// let err = tranche.take_front(5).unwrap_err();
+    /// assert_eq!(err.needed(), 5);
+    /// assert_eq!(err.len(), 4);
+    /// ```
+    pub fn take_front(&mut self, n: usize) -> Result<ChainedFront<'a>, UnexpectedEndError> {
+        let len = self.len;
+        if n > len {
+            return Err(UnexpectedEndError::new(n, len));
+        }
+        if n <= self.front.len() {
+            let front = self.front.clone().take_front(n)?;
+            self.advance(n);
+            return Ok(ChainedFront::Contiguous(front));
+        }
+        let front = Self {
+            front: self.front.clone(),
+            rest: self.rest,
+            len: n,
+            offset: self.offset,
+        };
+        self.advance(n);
+        Ok(ChainedFront::Chained(front))
+    }
+
+    /// Fills `dest` by taking its length in bytes out of the tranche.
+    ///
+    /// The internal offset is incremented accordingly.
+    ///
+    /// Returns `Err(_)` if `self` is not long enough, in which case nothing
+    /// is taken.
+    pub fn take_into(&mut self, dest: &mut [u8]) -> Result<(), UnexpectedEndError> {
+        let len = self.len;
+        if dest.len() > len {
+            return Err(UnexpectedEndError::new(dest.len(), len));
+        }
+        let mut written = 0;
+        while written < dest.len() {
+            let n = cmp::min(dest.len() - written, self.front.len());
+            let chunk = self.front.clone().take_front(n)?.as_slice();
+            dest[written..written + n].copy_from_slice(chunk);
+            written += n;
+            self.advance(n);
+        }
+        Ok(())
+    }
+
+    /// Advances the tranche by `n` bytes, which must not be more than what
+    /// is left in it.
+    fn advance(&mut self, mut n: usize) {
+        debug_assert!(n <= self.len);
+        self.offset += n;
+        while n > 0 {
+            let taken = cmp::min(n, self.front.len());
+            self.front.take_front(taken).unwrap();
+            self.len -= taken;
+            n -= taken;
+            self.skip_empty_segments();
+        }
+    }
+
+    /// Moves on to the next non-empty segment if the front one is empty.
+    fn skip_empty_segments(&mut self) {
+        let remaining = self.len - self.front.len();
+        while self.front.is_empty() && remaining > 0 {
+            let (segment, rest) = match self.rest.split_first() {
+                Some(split) => split,
+                None => break,
+            };
+            self.front = BufTranche::new(&segment[..cmp::min(segment.len(), remaining)]);
+            self.rest = rest;
+        }
+    }
+}
+
+impl<'a> ChainedFront<'a> {
+    /// Returns the number of bytes in the front.
+    pub fn len(&self) -> usize {
+        match self {
+            ChainedFront::Contiguous(tranche) => tranche.len(),
+            ChainedFront::Chained(tranche) => tranche.len(),
+        }
+    }
+
+    /// Returns `true` if the front has a length of 0.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Converts the front into a chained tranche, whether it is contiguous
+    /// or not.
+    ///
+    /// The offset of a contiguous front starts back at 0.
+    pub fn into_chained(self) -> ChainedBufTranche<'a> {
+        match self {
+            ChainedFront::Contiguous(tranche) => tranche.into(),
+            ChainedFront::Chained(tranche) => tranche,
+        }
+    }
+}
+
+impl<'a> From<BufTranche<'a>> for ChainedBufTranche<'a> {
+    fn from(tranche: BufTranche<'a>) -> Self {
+        let len = tranche.len();
+        Self {
+            front: tranche,
+            rest: &[],
+            len,
+            offset: 0,
+        }
+    }
+}
+
+impl fmt::Debug for ChainedBufTranche<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_list().entries(self.chunks().flatten()).finish()
+    }
+}
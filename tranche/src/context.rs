@@ -0,0 +1,218 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::error;
+use core::fmt;
+
+use crate::error::Error;
+
+/// How many labels a context error can hold without the `alloc` feature.
+#[cfg(not(feature = "alloc"))]
+const MAX_DEPTH: usize = 2;
+
+/// Attaches labels to errors, see [`ContextError`](struct.ContextError.html).
+pub trait Context<T> {
+    /// Labels the error, if any, with the part of the input that was being
+    /// parsed.
+    fn context(self, name: &'static str) -> Result<T, ContextError>;
+
+    /// Labels the error, if any, with the part of the input that was being
+    /// parsed, along with its index, as in `chunk[3]`.
+    fn context_index(self, name: &'static str, index: usize) -> Result<T, ContextError>;
+}
+
+impl<T, E> Context<T> for Result<T, E>
+where
+    E: Into<ContextError>,
+{
+    fn context(self, name: &'static str) -> Result<T, ContextError> {
+        self.map_err(|error| error.into().push(ContextLabel { name, index: None }))
+    }
+
+    fn context_index(self, name: &'static str, index: usize) -> Result<T, ContextError> {
+        self.map_err(|error| {
+            let index = Some(index);
+            error.into().push(ContextLabel { name, index })
+        })
+    }
+}
+
+/// An error along with the path of labels leading to where it happened.
+///
+/// Labels are attached with the [`Context`](trait.Context.html) trait as
+/// errors bubble up through nested parsers, and are rendered outermost first
+/// by `Display`, followed by the message of the error. Since that message is
+/// already included, `source` is that of the error rather than the error
+/// itself. Without the `alloc` feature, labels are stored inline and only
+/// the 2 innermost ones are kept, so that this error stays within 128 bytes.
+///
+/// # Examples
+///
+/// ```
+/// # use tranche::{BufTranche, Context, ContextError};
+/// fn chunk(t: &mut BufTranche) -> Result<u16, ContextError> {
+///     t.take_u8().context("tag")?;
+///     t.take_u16_be().context("length")
+/// }
+///
+/// fn file(t: &mut BufTranche) -> Result<(), ContextError> {
+///     for i in 0..t.take_u8().context("count")? {
+///         chunk(t).context_index("chunk", i as usize)?;
+///     }
+///     Ok(())
+/// }
+///
+/// let err = file(&mut BufTranche::new(&[2, 1, 0, 0, 2, 0])).unwrap_err();
+/// assert_eq!(
+///     err.to_string(),
+///     "chunk[1] > length: unexpected end (needed 2, got 1)",
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct ContextError {
+    error: Error,
+    #[cfg(feature = "alloc")]
+    labels: Vec<ContextLabel>,
+    #[cfg(not(feature = "alloc"))]
+    labels: [(&'static str, usize); MAX_DEPTH],
+    #[cfg(not(feature = "alloc"))]
+    indexed: u8,
+    #[cfg(not(feature = "alloc"))]
+    depth: u8,
+    truncated: bool,
+}
+
+/// A label attached to a [`ContextError`](struct.ContextError.html).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ContextLabel {
+    name: &'static str,
+    index: Option<usize>,
+}
+
+impl ContextError {
+    /// Creates a new context error without any label.
+    pub fn new(error: impl Into<Error>) -> Self {
+        Self {
+            error: error.into(),
+            #[cfg(feature = "alloc")]
+            labels: Vec::new(),
+            #[cfg(not(feature = "alloc"))]
+            labels: [("", 0); MAX_DEPTH],
+            #[cfg(not(feature = "alloc"))]
+            indexed: 0,
+            #[cfg(not(feature = "alloc"))]
+            depth: 0,
+            truncated: false,
+        }
+    }
+
+    /// Returns the underlying error.
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+
+    /// Consumes the context error, returning the underlying error.
+    pub fn into_error(self) -> Error {
+        self.error
+    }
+
+    /// Returns the labels attached to the error, outermost first.
+    #[cfg(feature = "alloc")]
+    pub fn labels(&self) -> impl DoubleEndedIterator<Item = ContextLabel> + '_ {
+        self.labels.iter().rev().copied()
+    }
+
+    /// Returns the labels attached to the error, outermost first.
+    #[cfg(not(feature = "alloc"))]
+    pub fn labels(&self) -> impl DoubleEndedIterator<Item = ContextLabel> + '_ {
+        (0..self.depth as usize).rev().map(move |i| {
+            let (name, index) = self.labels[i];
+            let index = Some(index).filter(|_| self.indexed & 1 << i != 0);
+            ContextLabel { name, index }
+        })
+    }
+
+    /// Returns `true` if outer labels were dropped for lack of space.
+    ///
+    /// This never happens with the `alloc` feature.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Attaches an outer label to the error.
+    fn push(mut self, label: ContextLabel) -> Self {
+        #[cfg(feature = "alloc")]
+        self.labels.push(label);
+        #[cfg(not(feature = "alloc"))]
+        match self.labels.get_mut(self.depth as usize) {
+            Some(slot) => {
+                *slot = (label.name, label.index.unwrap_or(0));
+                if label.index.is_some() {
+                    self.indexed |= 1 << self.depth;
+                }
+                self.depth += 1;
+            }
+            None => self.truncated = true,
+        }
+        self
+    }
+}
+
+impl ContextLabel {
+    /// Returns the name of the label.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the index of the label, if any.
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+}
+
+impl<E> From<E> for ContextError
+where
+    E: Into<Error>,
+{
+    fn from(error: E) -> Self {
+        Self::new(error)
+    }
+}
+
+impl fmt::Display for ContextError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        if self.truncated {
+            fmt.write_str("... > ")?;
+        }
+        let mut labels = self.labels();
+        if let Some(label) = labels.next() {
+            label.fmt(fmt)?;
+            for label in labels {
+                write!(fmt, " > {}", label)?;
+            }
+            fmt.write_str(": ")?;
+        }
+        self.error.fmt(fmt)
+    }
+}
+
+impl fmt::Display for ContextLabel {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(self.name)?;
+        if let Some(index) = self.index {
+            write!(fmt, "[{}]", index)?;
+        }
+        Ok(())
+    }
+}
+
+impl error::Error for ContextError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        error::Error::source(&self.error)
+    }
+}
@@ -4,12 +4,18 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+#[cfg(feature = "passive")]
+use core::array;
+use core::convert::TryFrom;
 use core::fmt;
 use core::marker::PhantomData as marker;
 use core::mem;
+use core::ops::Range;
 use core::ptr::NonNull;
 use core::slice;
 
+use crate::error::{ExpectError, MismatchError, TakeLenError, TrailingDataError};
+
 #[cfg(feature = "passive")]
 use passive::{AlwaysAligned, AlwaysValid, Immutable};
 
@@ -30,10 +36,14 @@ unsafe impl<T> Sync for Tranche<'_, T> where T: Sync {}
 ///
 /// Based tranches are just like tranches, with the addition of an `offset`
 /// method which returns how many items were taken from the front of
-/// the original based tranche returned from `BasedTranche::new`.
+/// the original based tranche returned from `BasedTranche::new`. Errors
+/// returned by based tranches carry that offset too. They also remember where
+/// that original based tranche ended, and can thus move back and forth within
+/// it with `seek_to`.
 pub struct BasedTranche<'a, T> {
     pub(crate) inner: Tranche<'a, T>,
     base: *const T,
+    extent: usize,
 }
 unsafe impl<T> Send for BasedTranche<'_, T> where T: Sync {}
 unsafe impl<T> Sync for BasedTranche<'_, T> where T: Sync {}
@@ -46,8 +56,8 @@ pub type BufTranche<'a> = Tranche<'a, u8>;
 
 /// A based tranche of bytes, equipped with many convenience methods.
 ///
-/// This type implements `std::io::Read` and `std::io::BufRead` when the `std`
-/// feature is enabled.
+/// This type implements `std::io::Read`, `std::io::BufRead` and
+/// `std::io::Seek` when the `std` feature is enabled.
 pub type BasedBufTranche<'a> = BasedTranche<'a, u8>;
 
 impl<'a, T> Tranche<'a, T> {
@@ -119,7 +129,7 @@ impl<'a, T> Tranche<'a, T> {
     /// ```
     pub fn take_first(&mut self) -> Result<&'a T, UnexpectedEndError> {
         if (*self).is_empty() {
-            return Err(UnexpectedEndError { needed: 1, len: 0 });
+            return Err(UnexpectedEndError::new(1, 0));
         }
         unsafe { Ok(&*self.post_inc_start(1)) }
     }
@@ -144,14 +154,134 @@ impl<'a, T> Tranche<'a, T> {
     pub fn take_front(&mut self, n: usize) -> Result<Self, UnexpectedEndError> {
         let len = self.len();
         if n > len {
-            return Err(UnexpectedEndError { needed: n, len });
+            return Err(UnexpectedEndError::new(n, len));
         }
         let start = unsafe { NonNull::new_unchecked(self.post_inc_start(n) as *mut _) };
-        let end = self.as_ptr();
+        let end = if mem::size_of::<T>() == 0 {
+            (start.as_ptr() as *const u8).wrapping_add(n) as *const T
+        } else {
+            self.as_ptr()
+        };
         let marker = self.marker;
         Ok(Self { start, end, marker })
     }
 
+    /// Takes the first `n` elements out of the tranche, where `n` is a `u64`
+    /// such as a length field read from the input.
+    ///
+    /// Returns `Err(_)` if `n` does not fit in a `usize` or if `self` is not
+    /// long enough.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tranche::{TakeLenError, Tranche};
+    /// let mut v = Tranche::new(&[10, 40, 30]);
+    /// assert_eq!(v.take_front_u64(2).unwrap().as_slice(), &[10, 40]);
+    /// assert!(matches!(v.take_front_u64(2), Err(TakeLenError::UnexpectedEnd(_))));
+    /// ```
+    pub fn take_front_u64(&mut self, n: u64) -> Result<Self, TakeLenError> {
+        let n = usize::try_from(n).map_err(|_| OverflowError::new())?;
+        Ok(self.take_front(n)?)
+    }
+
+    /// Takes the first `count * size` elements out of the tranche, such as
+    /// `count` records of `size` bytes each declared by the input.
+    ///
+    /// Returns `Err(_)` if `count * size` overflows a `usize` or if `self` is
+    /// not long enough.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tranche::{BufTranche, TakeLenError};
+    /// let mut v = BufTranche::new(&[0; 16]);
+    /// assert_eq!(v.take_front_count(3, 4).unwrap().len(), 12);
+    /// assert!(matches!(v.take_front_count(usize::MAX, 2), Err(TakeLenError::Overflow(_))));
+    /// ```
+    pub fn take_front_count(&mut self, count: usize, size: usize) -> Result<Self, TakeLenError> {
+        let n = count.checked_mul(size).ok_or_else(OverflowError::new)?;
+        Ok(self.take_front(n)?)
+    }
+
+    /// Checks that the tranche was fully consumed.
+    ///
+    /// Returns `Err(_)` with the number of elements left otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tranche::Tranche;
+    /// let mut v = Tranche::new(&[10, 40, 30]);
+    /// v.take_front(2).unwrap();
+    /// assert_eq!(v.finish().unwrap_err().len(), 1);
+    /// v.take_first().unwrap();
+    /// assert!(v.finish().is_ok());
+    /// ```
+    pub fn finish(&self) -> Result<(), TrailingDataError> {
+        match self.len() {
+            0 => Ok(()),
+            len => Err(TrailingDataError::new(len)),
+        }
+    }
+
+    /// Runs a parser on the first `n` elements of the tranche, which it must
+    /// consume entirely.
+    ///
+    /// Returns what the parser returned, or `Err(_)` if `self` is not long
+    /// enough, if the parser failed or if it left elements over. The `n`
+    /// elements are taken out of `self` only if everything succeeded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tranche::{BufTranche, Error};
+    /// let mut tranche = BufTranche::new(&[0, 1, 0, 2, 0xff]);
+    /// let pair = tranche.parse_exact(4, |t| Ok::<_, Error>((t.take_u16_be()?, t.take_u16_be()?)));
+    /// assert_eq!(pair.unwrap(), (1, 2));
+    ///
+    /// let res = tranche.parse_exact(1, |_| Ok::<_, Error>(()));
+    /// assert!(matches!(res, Err(Error::TrailingData(_))));
+    /// assert_eq!(tranche.len(), 1);
+    /// ```
+    pub fn parse_exact<R, E>(
+        &mut self,
+        n: usize,
+        f: impl FnOnce(&mut Self) -> Result<R, E>,
+    ) -> Result<R, E>
+    where
+        E: From<UnexpectedEndError> + From<TrailingDataError>,
+    {
+        let mut rest = self.clone();
+        let mut front = rest.take_front(n)?;
+        let value = f(&mut front)?;
+        front.finish()?;
+        *self = rest;
+        Ok(value)
+    }
+
+    /// Runs a parser on the whole tranche, which it must consume entirely.
+    ///
+    /// Returns what the parser returned, or `Err(_)` if the parser failed or
+    /// if it left elements over.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tranche::{BufTranche, Error};
+    /// let parse = |t: &mut BufTranche| Ok::<_, Error>(t.take_u16_le()?);
+    /// assert_eq!(BufTranche::new(&[1, 0]).parse_all(parse).unwrap(), 1);
+    /// assert!(BufTranche::new(&[1, 0, 0]).parse_all(parse).is_err());
+    /// ```
+    pub fn parse_all<R, E>(&mut self, f: impl FnOnce(&mut Self) -> Result<R, E>) -> Result<R, E>
+    where
+        E: From<TrailingDataError>,
+    {
+        let value = f(self)?;
+        self.finish()?;
+        Ok(value)
+    }
+
     /// Views the tranche's buffer as a slice.
     ///
     /// This has the same lifetime as the original buffer, and so the tranche
@@ -195,6 +325,122 @@ impl<'a, T> Tranche<'a, T> {
     }
 }
 
+impl<'a, T> Tranche<'a, T>
+where
+    T: PartialEq,
+{
+    /// Takes the literal `expected` out of the front of the tranche.
+    ///
+    /// Returns `Err(_)` with what was found instead if `self` doesn't start
+    /// with `expected`, or if it is not long enough, in which case the
+    /// tranche is left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tranche::{BufTranche, ExpectError};
+    /// let mut tranche = BufTranche::new(b"GIF89a\x01\0");
+    /// assert!(tranche.expect(b"GIF").is_ok());
+    ///
+    /// let err = tranche.expect(b"87a").unwrap_err();
+    /// assert_eq!(err.to_string(), "mismatch (expected [38, 37, 61], found [38, 39, 61])");
+    /// assert!(matches!(tranche.expect(b"89a\x01\0\0"), Err(ExpectError::UnexpectedEnd(_))));
+    /// assert_eq!(tranche.as_slice(), b"89a\x01\0");
+    /// ```
+    pub fn expect<'e>(&mut self, expected: &'e [T]) -> Result<(), ExpectError<&'e [T]>>
+    where
+        'a: 'e,
+    {
+        let slice = self.as_slice();
+        let found = &slice[..slice.len().min(expected.len())];
+        if found != &expected[..found.len()] {
+            return Err(MismatchError::new(expected, found, expected.len()).into());
+        }
+        self.take_front(expected.len())?;
+        Ok(())
+    }
+
+    /// Takes the literal `expected` out of the back of the tranche.
+    ///
+    /// Returns `Err(_)` with what was found instead if `self` doesn't end
+    /// with `expected`, or if it is not long enough, in which case the
+    /// tranche is left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tranche::BufTranche;
+    /// let mut tranche = BufTranche::new(b"%PDF-1.7\n%%EOF\n");
+    /// assert!(tranche.expect_back(b"%%EOF\n").is_ok());
+    /// assert_eq!(tranche.as_slice(), b"%PDF-1.7\n");
+    ///
+    /// let err = tranche.expect_back(b"%%EOF\n").unwrap_err();
+    /// assert_eq!(err.to_string(), "mismatch (expected [25, 25, 45, 4f, 46, a], found [46, 2d, 31, 2e, 37, a])");
+    /// ```
+    pub fn expect_back<'e>(&mut self, expected: &'e [T]) -> Result<(), ExpectError<&'e [T]>>
+    where
+        'a: 'e,
+    {
+        let slice = self.as_slice();
+        let found = &slice[slice.len().saturating_sub(expected.len())..];
+        if found != &expected[expected.len() - found.len()..] {
+            return Err(MismatchError::new(expected, found, expected.len()).into());
+        }
+        if found.len() < expected.len() {
+            return Err(UnexpectedEndError::new(expected.len(), found.len()).into());
+        }
+        if mem::size_of::<T>() == 0 {
+            self.end = (self.end as *const u8).wrapping_sub(expected.len()) as *const T;
+        } else {
+            self.end = unsafe { self.end.sub(expected.len()) };
+        }
+        Ok(())
+    }
+
+    /// Takes the first element out of the tranche if it is equal to `value`.
+    ///
+    /// Returns whether it was taken.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tranche::BufTranche;
+    /// let mut tranche = BufTranche::new(b"-42");
+    /// assert!(tranche.eat(&b'-'));
+    /// assert!(!tranche.eat(&b'-'));
+    /// assert_eq!(tranche.as_slice(), b"42");
+    /// ```
+    pub fn eat(&mut self, value: &T) -> bool {
+        let eaten = self.as_slice().first() == Some(value);
+        if eaten {
+            self.take_first().unwrap();
+        }
+        eaten
+    }
+
+    /// Takes the literal `slice` out of the front of the tranche if the
+    /// tranche starts with it.
+    ///
+    /// Returns whether it was taken.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tranche::BufTranche;
+    /// let mut tranche = BufTranche::new(b"\xef\xbb\xbfhello");
+    /// assert!(tranche.eat_slice(b"\xef\xbb\xbf"));
+    /// assert!(!tranche.eat_slice(b"help"));
+    /// assert_eq!(tranche.as_slice(), b"hello");
+    /// ```
+    pub fn eat_slice(&mut self, slice: &[T]) -> bool {
+        let eaten = self.as_slice().starts_with(slice);
+        if eaten {
+            self.take_front(slice.len()).unwrap();
+        }
+        eaten
+    }
+}
+
 impl<'a, T> BasedTranche<'a, T> {
     /// Creates a new based tranche of `T`.
     ///
@@ -265,7 +511,8 @@ impl<'a, T> BasedTranche<'a, T> {
     /// assert_eq!(err.len(), 0);
     /// ```
     pub fn take_first(&mut self) -> Result<&'a T, UnexpectedEndError> {
-        self.inner.take_first()
+        let offset = self.offset();
+        self.inner.take_first().map_err(|err| err.at(offset))
     }
 
     /// Takes the first `n` elements out of the tranche.
@@ -286,9 +533,170 @@ impl<'a, T> BasedTranche<'a, T> {
     /// assert_eq!(err.len(), 1);
     /// ```
     pub fn take_front(&mut self, n: usize) -> Result<Self, UnexpectedEndError> {
-        let inner = self.inner.take_front(n)?;
-        let base = self.base;
-        Ok(Self { inner, base })
+        let offset = self.offset();
+        let inner = self.inner.take_front(n).map_err(|err| err.at(offset))?;
+        let base = if mem::size_of::<T>() == 0 {
+            (inner.end as *const u8).wrapping_add(offset) as *const T
+        } else {
+            self.base
+        };
+        let extent = offset + n;
+        Ok(Self {
+            inner,
+            base,
+            extent,
+        })
+    }
+
+    /// Takes the first `n` elements out of the tranche, where `n` is a `u64`.
+    ///
+    /// Returns `Err(_)` carrying the current offset if `n` does not fit in a
+    /// `usize` or if `self` is not long enough. See
+    /// [`Tranche::take_front_u64`](struct.Tranche.html#method.take_front_u64).
+    pub fn take_front_u64(&mut self, n: u64) -> Result<Self, TakeLenError> {
+        let offset = self.offset();
+        let n = usize::try_from(n)
+            .map_err(|_| TakeLenError::from(OverflowError::new()).at(offset, 0))?;
+        Ok(self.take_front(n)?)
+    }
+
+    /// Takes the first `count * size` elements out of the tranche.
+    ///
+    /// Returns `Err(_)` carrying the current offset if `count * size`
+    /// overflows a `usize` or if `self` is not long enough. See
+    /// [`Tranche::take_front_count`](struct.Tranche.html#method.take_front_count).
+    pub fn take_front_count(&mut self, count: usize, size: usize) -> Result<Self, TakeLenError> {
+        let offset = self.offset();
+        let n = count
+            .checked_mul(size)
+            .ok_or_else(|| TakeLenError::from(OverflowError::new()).at(offset, 0))?;
+        Ok(self.take_front(n)?)
+    }
+
+    /// Returns the offset at which this based tranche ends, which is the
+    /// length of the original based tranche, or the offset at which it ended
+    /// if it was itself taken out of another one with `take_front`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tranche::BasedTranche;
+    /// let mut v = BasedTranche::new(&[10, 40, 30, 20]);
+    /// let front = v.take_front(3).unwrap();
+    /// assert_eq!(v.extent(), 4);
+    /// assert_eq!(front.extent(), 3);
+    /// ```
+    pub fn extent(&self) -> usize {
+        self.extent
+    }
+
+    /// Moves the start of the tranche to the given offset, which may be
+    /// before the current one, and its end back to `extent`.
+    ///
+    /// Returns `Err(_)` if the offset is past `extent`, in which case the
+    /// tranche is left untouched. The error is expressed from the start of
+    /// the original tranche: its offset is 0, it needed `offset` elements and
+    /// got `extent` of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tranche::BasedTranche;
+    /// let mut v = BasedTranche::new(&[10, 40, 30]);
+    /// v.seek_to(2).unwrap();
+    /// assert_eq!(v.as_slice(), &[30]);
+    /// v.seek_to(1).unwrap();
+    /// assert_eq!(v.as_slice(), &[40, 30]);
+    ///
+    /// let err = v.seek_to(4).unwrap_err();
+    /// assert_eq!(err.offset(), Some(0));
+    /// assert_eq!(err.needed(), 4);
+    /// assert_eq!(err.len(), 3);
+    /// assert_eq!(v.offset(), 1);
+    /// ```
+    pub fn seek_to(&mut self, offset: usize) -> Result<(), UnexpectedEndError> {
+        if offset > self.extent {
+            return Err(UnexpectedEndError::new(offset, self.extent).at(0));
+        }
+        if mem::size_of::<T>() == 0 {
+            self.inner.end = (self.base as *const u8).wrapping_sub(offset) as *const T;
+        } else {
+            unsafe {
+                self.inner.start = NonNull::new_unchecked(self.base.add(offset) as *mut T);
+                self.inner.end = self.base.add(self.extent);
+            }
+        }
+        Ok(())
+    }
+
+    /// Moves the start of the tranche back to offset 0, and its end back to
+    /// `extent`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tranche::BasedTranche;
+    /// let mut v = BasedTranche::new(&[10, 40, 30]);
+    /// v.take_front(2).unwrap();
+    /// v.rewind();
+    /// assert_eq!(v.offset(), 0);
+    /// assert_eq!(v.as_slice(), &[10, 40, 30]);
+    /// ```
+    pub fn rewind(&mut self) {
+        self.seek_to(0).unwrap();
+    }
+
+    /// Checks that the tranche was fully consumed.
+    ///
+    /// Returns `Err(_)` with the number of elements left and the current
+    /// offset otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tranche::BasedTranche;
+    /// let mut v = BasedTranche::new(&[10, 40, 30]);
+    /// v.take_front(2).unwrap();
+    /// let err = v.finish().unwrap_err();
+    /// assert_eq!((err.len(), err.offset()), (1, Some(2)));
+    /// ```
+    pub fn finish(&self) -> Result<(), TrailingDataError> {
+        let offset = self.offset();
+        self.inner.finish().map_err(|err| err.at(offset))
+    }
+
+    /// Runs a parser on the first `n` elements of the tranche, which it must
+    /// consume entirely.
+    ///
+    /// The parser is given a based tranche whose offsets are relative to the
+    /// same origin as `self`. See
+    /// [`Tranche::parse_exact`](struct.Tranche.html#method.parse_exact).
+    pub fn parse_exact<R, E>(
+        &mut self,
+        n: usize,
+        f: impl FnOnce(&mut Self) -> Result<R, E>,
+    ) -> Result<R, E>
+    where
+        E: From<UnexpectedEndError> + From<TrailingDataError>,
+    {
+        let mut rest = self.clone();
+        let mut front = rest.take_front(n)?;
+        let value = f(&mut front)?;
+        front.finish()?;
+        *self = rest;
+        Ok(value)
+    }
+
+    /// Runs a parser on the whole tranche, which it must consume entirely.
+    ///
+    /// See [`Tranche::parse_all`](struct.Tranche.html#method.parse_all).
+    pub fn parse_all<R, E>(&mut self, f: impl FnOnce(&mut Self) -> Result<R, E>) -> Result<R, E>
+    where
+        E: From<TrailingDataError>,
+    {
+        let value = f(self)?;
+        self.finish()?;
+        Ok(value)
     }
 
     /// Views the tranche's buffer as a slice.
@@ -324,6 +732,56 @@ impl<'a, T> BasedTranche<'a, T> {
     }
 }
 
+impl<'a, T> BasedTranche<'a, T>
+where
+    T: PartialEq,
+{
+    /// Takes the literal `expected` out of the front of the tranche.
+    ///
+    /// The internal offset is incremented accordingly.
+    ///
+    /// Returns `Err(_)` carrying the current offset if `self` doesn't start
+    /// with `expected` or if it is not long enough. See
+    /// [`Tranche::expect`](struct.Tranche.html#method.expect).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tranche::BasedBufTranche;
+    /// let mut tranche = BasedBufTranche::new(b"RIFF\0\0\0\0WAVE");
+    /// tranche.expect(b"RIFF").unwrap();
+    /// tranche.take_front(4).unwrap();
+    /// let err = tranche.expect(b"AVI ").unwrap_err();
+    /// assert_eq!(err.to_string(), "mismatch at offset 0x8 (expected [41, 56, 49, 20], found [57, 41, 56, 45])");
+    /// ```
+    pub fn expect<'e>(&mut self, expected: &'e [T]) -> Result<(), ExpectError<&'e [T]>>
+    where
+        'a: 'e,
+    {
+        let offset = self.offset();
+        self.inner.expect(expected).map_err(|err| err.at(offset))
+    }
+
+    /// Takes the first element out of the tranche if it is equal to `value`.
+    ///
+    /// The internal offset is incremented accordingly.
+    ///
+    /// Returns whether it was taken.
+    pub fn eat(&mut self, value: &T) -> bool {
+        self.inner.eat(value)
+    }
+
+    /// Takes the literal `slice` out of the front of the tranche if the
+    /// tranche starts with it.
+    ///
+    /// The internal offset is incremented accordingly.
+    ///
+    /// Returns whether it was taken.
+    pub fn eat_slice(&mut self, slice: &[T]) -> bool {
+        self.inner.eat_slice(slice)
+    }
+}
+
 #[cfg(feature = "passive")]
 impl<'a> BufTranche<'a> {
     /// Takes the first `n` elements of type `T` out of the tranche.
@@ -332,6 +790,9 @@ impl<'a> BufTranche<'a> {
     /// a tranche of `T` values, or `Err(_)` if it is not long enough. The error
     /// details are expressed in terms of the size of `T`, not the size of `u8`.
     ///
+    /// If `T` is zero-sized, this never fails and nothing is taken out of
+    /// the tranche.
+    ///
     /// # Examples
     ///
     /// ```
@@ -349,31 +810,166 @@ impl<'a> BufTranche<'a> {
     where
         T: AlwaysAligned + AlwaysValid + Immutable,
     {
-        if mem::size_of::<T>() == 0 {
-            return Ok(Tranche::new(unsafe {
-                slice::from_raw_parts(self.as_ptr() as *const T, n)
-            }));
+        unsafe { self.take_front_as_unchecked(n) }
+    }
+
+    /// Takes the first `n` elements of type `T` out of the tranche, checking
+    /// that the tranche is suitably aligned for `T`.
+    ///
+    /// This is like `take_front_as`, but for types with an alignment greater
+    /// than 1. Returns `Err(TakeAsError::Misaligned(_))` if the start of the
+    /// tranche is not aligned for `T`, and `Err(TakeAsError::UnexpectedEnd(_))`
+    /// if it is not long enough. Nothing is taken out of the tranche on error.
+    ///
+    /// If `T` is zero-sized, this never fails and nothing is taken out of
+    /// the tranche.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tranche::{BufTranche, TakeAsError};
+    /// let words = [0x0102_0304u32, 0x0506_0708];
+    /// let bytes = unsafe { core::slice::from_raw_parts(words.as_ptr() as *const u8, 8) };
+    ///
+    /// let mut v = BufTranche::new(bytes);
+    /// let first = v.take_front_as_aligned::<u32>(1).unwrap();
+    /// assert_eq!(first.as_slice(), &[0x0102_0304]);
+    ///
+    /// v.take_u8().unwrap();
+    /// match v.take_front_as_aligned::<u32>(1).unwrap_err() {
+    ///     TakeAsError::Misaligned(err) => {
+    ///         assert_eq!(err.align(), 4);
+    ///         assert_eq!(err.misalignment(), 1);
+    ///     }
+    ///     TakeAsError::UnexpectedEnd(_) => unreachable!(),
+    /// }
+    /// assert_eq!(v.len(), 3);
+    /// ```
+    pub fn take_front_as_aligned<T>(&mut self, n: usize) -> Result<Tranche<'a, T>, TakeAsError>
+    where
+        T: AlwaysValid + Immutable,
+    {
+        let align = mem::align_of::<T>();
+        let misalignment = self.as_ptr() as usize % align;
+        if mem::size_of::<T>() != 0 && misalignment != 0 {
+            return Err(MisalignedError::new(align, misalignment).into());
         }
-        let len = ptr_distance(self.as_ptr() as *const T, self.end as *const T);
-        if n > len {
-            return Err(UnexpectedEndError { needed: n, len });
+        Ok(unsafe { self.take_front_as_unchecked(n)? })
+    }
+
+    /// Takes the first `N` elements of type `T` out of the tranche by copying
+    /// them into an array.
+    ///
+    /// This works regardless of the alignment of `T` and of the tranche.
+    /// Returns `Err(_)` if `self` is not long enough, in which case the error
+    /// details are expressed in terms of the size of `T`.
+    ///
+    /// If `T` is zero-sized, this never fails and nothing is taken out of
+    /// the tranche.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tranche::BufTranche;
+    /// let mut v = BufTranche::new(&[0, 1, 0, 0, 0, 2, 0, 0, 0]);
+    /// v.take_u8().unwrap();
+    ///
+    /// let words = v.take_front_as_unaligned::<u32, 2>().unwrap();
+    /// assert_eq!(words, [u32::from_le(1), u32::from_le(2)]);
+    /// assert!(v.is_empty());
+    ///
+    /// let err = v.take_front_as_unaligned::<u16, 1>().unwrap_err();
+    /// assert_eq!(err.needed(), 1);
+    /// assert_eq!(err.len(), 0);
+    /// ```
+    pub fn take_front_as_unaligned<T, const N: usize>(
+        &mut self,
+    ) -> Result<[T; N], UnexpectedEndError>
+    where
+        T: AlwaysValid + Copy,
+    {
+        let size = mem::size_of::<T>();
+        if let Some(len) = self.len().checked_div(size) {
+            if N > len {
+                return Err(UnexpectedEndError::new(N, len));
+            }
         }
-        unsafe {
-            let start =
-                NonNull::new_unchecked(self.post_inc_start(n * mem::size_of::<T>()) as *mut T);
-            let end = self.as_ptr() as *const T;
-            Ok(Tranche { start, end, marker })
+        let ptr = unsafe { self.post_inc_start(N * size) } as *const T;
+        Ok(array::from_fn(|i| unsafe { ptr.add(i).read_unaligned() }))
+    }
+
+    /// The start of the tranche must be aligned for `T`, unless `T` is
+    /// zero-sized.
+    unsafe fn take_front_as_unchecked<T>(
+        &mut self,
+        n: usize,
+    ) -> Result<Tranche<'a, T>, UnexpectedEndError> {
+        let size = mem::size_of::<T>();
+        if size == 0 {
+            let start = NonNull::dangling();
+            let end = (start.as_ptr() as *const u8).wrapping_add(n) as *const T;
+            return Ok(Tranche { start, end, marker });
         }
+        let len = self.len() / size;
+        if n > len {
+            return Err(UnexpectedEndError::new(n, len));
+        }
+        let start = NonNull::new_unchecked(self.post_inc_start(n * size) as *mut T);
+        let end = self.as_ptr() as *const T;
+        Ok(Tranche { start, end, marker })
     }
 }
 
 #[cfg(feature = "passive")]
 impl<'a> BasedBufTranche<'a> {
+    /// Takes the first `n` elements of type `T` out of the tranche.
+    ///
+    /// The internal offset is incremented by the number of bytes taken.
+    ///
+    /// See [`BufTranche::take_front_as`](type.BufTranche.html#method.take_front_as).
     pub fn take_front_as<T>(&mut self, n: usize) -> Result<Tranche<'a, T>, UnexpectedEndError>
     where
         T: AlwaysAligned + AlwaysValid + Immutable,
     {
-        self.inner.take_front_as(n)
+        let offset = self.offset();
+        self.inner.take_front_as(n).map_err(|err| err.at(offset))
+    }
+
+    /// Takes the first `n` elements of type `T` out of the tranche, checking
+    /// that the tranche is suitably aligned for `T`.
+    ///
+    /// The internal offset is incremented by the number of bytes taken.
+    ///
+    /// See [`BufTranche::take_front_as_aligned`](type.BufTranche.html#method.take_front_as_aligned).
+    pub fn take_front_as_aligned<T>(&mut self, n: usize) -> Result<Tranche<'a, T>, TakeAsError>
+    where
+        T: AlwaysValid + Immutable,
+    {
+        let offset = self.offset();
+        self.inner
+            .take_front_as_aligned(n)
+            .map_err(|err| match err {
+                TakeAsError::UnexpectedEnd(err) => err.at(offset).into(),
+                TakeAsError::Misaligned(err) => err.at(offset).into(),
+            })
+    }
+
+    /// Takes the first `N` elements of type `T` out of the tranche by copying
+    /// them into an array.
+    ///
+    /// The internal offset is incremented by the number of bytes taken.
+    ///
+    /// See [`BufTranche::take_front_as_unaligned`](type.BufTranche.html#method.take_front_as_unaligned).
+    pub fn take_front_as_unaligned<T, const N: usize>(
+        &mut self,
+    ) -> Result<[T; N], UnexpectedEndError>
+    where
+        T: AlwaysValid + Copy,
+    {
+        let offset = self.offset();
+        self.inner
+            .take_front_as_unaligned()
+            .map_err(|err| err.at(offset))
     }
 }
 
@@ -381,11 +977,7 @@ impl<'a> BasedBufTranche<'a> {
 fn ptr_distance<T>(start: *const T, end: *const T) -> usize {
     let diff = (end as usize).wrapping_sub(start as usize);
     let size = mem::size_of::<T>();
-    if size == 0 {
-        diff
-    } else {
-        diff / size
-    }
+    diff.checked_div(size).unwrap_or(diff)
 }
 
 impl<T> Clone for Tranche<'_, T> {
@@ -396,9 +988,10 @@ impl<T> Clone for Tranche<'_, T> {
 
 impl<T> Clone for BasedTranche<'_, T> {
     fn clone(&self) -> Self {
-        let inner = self.inner.clone();
-        let base = self.base;
-        Self { inner, base }
+        Self {
+            inner: self.inner.clone(),
+            ..*self
+        }
     }
 }
 
@@ -428,7 +1021,12 @@ impl<'a, T> From<Tranche<'a, T>> for BasedTranche<'a, T> {
         } else {
             inner.as_ptr()
         };
-        Self { inner, base }
+        let extent = inner.len();
+        Self {
+            inner,
+            base,
+            extent,
+        }
     }
 }
 
@@ -455,9 +1053,27 @@ where
 pub struct UnexpectedEndError {
     needed: usize,
     len: usize,
+    offset: Option<usize>,
 }
 
+#[allow(clippy::len_without_is_empty)]
 impl UnexpectedEndError {
+    pub(crate) fn new(needed: usize, len: usize) -> Self {
+        Self {
+            needed,
+            len,
+            offset: None,
+        }
+    }
+
+    /// Records the offset at which the failed operation was attempted.
+    pub(crate) fn at(self, offset: usize) -> Self {
+        Self {
+            offset: Some(offset),
+            ..self
+        }
+    }
+
     /// Returns the number of elements that were needed from the tranche for
     /// the operation to succeed.
     pub fn needed(&self) -> usize {
@@ -469,14 +1085,159 @@ impl UnexpectedEndError {
     pub fn len(&self) -> usize {
         self.len
     }
+
+    /// Returns the offset at which the operation was attempted, if it was
+    /// attempted on a based tranche.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tranche::{BasedBufTranche, BufTranche};
+    /// let mut tranche = BasedBufTranche::from(BufTranche::new(&[0; 502]));
+    /// tranche.take_front(500).unwrap();
+    /// let err = tranche.take_u32_le().unwrap_err();
+    /// assert_eq!(err.offset(), Some(500));
+    /// assert_eq!(err.to_string(), "unexpected end at offset 0x1f4 (needed 4, got 2)");
+    ///
+    /// let err = BufTranche::new(&[]).take_u8().unwrap_err();
+    /// assert_eq!(err.offset(), None);
+    /// ```
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
 }
 
 impl fmt::Display for UnexpectedEndError {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str("unexpected end")?;
+        if let Some(offset) = self.offset {
+            write!(fmt, " at offset {:#x}", offset)?;
+        }
+        write!(fmt, " (needed {}, got {})", self.needed, self.len)
+    }
+}
+
+/// An error signalling that a tranche was not suitably aligned for the
+/// requested type.
+#[derive(Clone, Debug)]
+pub struct MisalignedError {
+    align: usize,
+    misalignment: usize,
+    offset: Option<usize>,
+}
+
+impl MisalignedError {
+    #[cfg(feature = "passive")]
+    pub(crate) fn new(align: usize, misalignment: usize) -> Self {
+        Self {
+            align,
+            misalignment,
+            offset: None,
+        }
+    }
+
+    /// Records the offset at which the failed operation was attempted.
+    #[cfg(feature = "passive")]
+    pub(crate) fn at(self, offset: usize) -> Self {
+        Self {
+            offset: Some(offset),
+            ..self
+        }
+    }
+
+    /// Returns the alignment that was required for the operation to succeed.
+    pub fn align(&self) -> usize {
+        self.align
+    }
+
+    /// Returns by how many bytes the start of the tranche was past the
+    /// previous aligned address.
+    pub fn misalignment(&self) -> usize {
+        self.misalignment
+    }
+
+    /// Returns the offset at which the operation was attempted, if it was
+    /// attempted on a based tranche.
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+}
+
+impl fmt::Display for MisalignedError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str("misaligned")?;
+        if let Some(offset) = self.offset {
+            write!(fmt, " at offset {:#x}", offset)?;
+        }
         write!(
             fmt,
-            "unexpected end (needed {}, got {})",
-            self.needed, self.len,
+            " (required alignment {}, off by {})",
+            self.align, self.misalignment,
         )
     }
 }
+
+/// An error signalling that a tranche of bytes could not be viewed as a
+/// tranche of another type.
+#[derive(Clone, Debug)]
+pub enum TakeAsError {
+    /// The end of the tranche was reached unexpectedly.
+    UnexpectedEnd(UnexpectedEndError),
+    /// The tranche was not suitably aligned.
+    Misaligned(MisalignedError),
+}
+
+impl From<UnexpectedEndError> for TakeAsError {
+    fn from(error: UnexpectedEndError) -> Self {
+        TakeAsError::UnexpectedEnd(error)
+    }
+}
+
+impl From<MisalignedError> for TakeAsError {
+    fn from(error: MisalignedError) -> Self {
+        TakeAsError::Misaligned(error)
+    }
+}
+
+impl fmt::Display for TakeAsError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TakeAsError::UnexpectedEnd(error) => error.fmt(fmt),
+            TakeAsError::Misaligned(error) => error.fmt(fmt),
+        }
+    }
+}
+
+/// An error signalling that a value did not fit in the integer type it had to
+/// be converted to.
+#[derive(Clone, Debug)]
+pub struct OverflowError {
+    pub(crate) span: Option<Range<usize>>,
+}
+
+impl OverflowError {
+    pub(crate) fn new() -> Self {
+        Self { span: None }
+    }
+
+    /// Records the offsets of the value which did not fit.
+    pub(crate) fn spanning(self, span: Range<usize>) -> Self {
+        Self { span: Some(span) }
+    }
+
+    /// Returns the offset at which the value which did not fit starts, if it
+    /// was read from a based tranche.
+    pub fn offset(&self) -> Option<usize> {
+        self.span.as_ref().map(|span| span.start)
+    }
+}
+
+impl fmt::Display for OverflowError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str("integer overflow")?;
+        if let Some(offset) = self.offset() {
+            write!(fmt, " at offset {:#x}", offset)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use core::cmp;
+#[cfg(feature = "alloc")]
+use core::convert::Infallible;
+
+use embedded_io::{BufRead, Error, ErrorKind, ErrorType, Read, ReadExactError, Write};
+
+#[cfg(feature = "alloc")]
+use crate::writer::VecWriter;
+use crate::{BasedBufTranche, BufTranche, SliceWriter, UnexpectedEndError};
+
+/// Unexpected ends only happen when reading, and have no dedicated kind in
+/// `embedded-io`, where reaching the end of a reader too early is signalled
+/// with `ReadExactError::UnexpectedEof` instead.
+impl Error for UnexpectedEndError {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
+
+impl ErrorType for BufTranche<'_> {
+    type Error = UnexpectedEndError;
+}
+
+impl Read for BufTranche<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let len = cmp::min(self.len(), buf.len());
+        buf[..len].copy_from_slice(self.take_front(len)?.as_slice());
+        Ok(len)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), ReadExactError<Self::Error>> {
+        let len = self.len();
+        match self.take_front(buf.len()) {
+            Ok(front) => {
+                buf.copy_from_slice(front.as_slice());
+                Ok(())
+            }
+            Err(_) => {
+                self.take_front(len).unwrap();
+                Err(ReadExactError::UnexpectedEof)
+            }
+        }
+    }
+}
+
+impl BufRead for BufTranche<'_> {
+    fn fill_buf(&mut self) -> Result<&[u8], Self::Error> {
+        Ok(self.as_slice())
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let amt = cmp::min(amt, self.len());
+        self.take_front(amt).unwrap();
+    }
+}
+
+impl ErrorType for BasedBufTranche<'_> {
+    type Error = UnexpectedEndError;
+}
+
+impl Read for BasedBufTranche<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        Read::read(&mut self.inner, buf)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), ReadExactError<Self::Error>> {
+        Read::read_exact(&mut self.inner, buf)
+    }
+}
+
+impl BufRead for BasedBufTranche<'_> {
+    fn fill_buf(&mut self) -> Result<&[u8], Self::Error> {
+        BufRead::fill_buf(&mut self.inner)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        BufRead::consume(&mut self.inner, amt)
+    }
+}
+
+/// A full writer is signalled with `ErrorKind::WriteZero`.
+impl ErrorType for SliceWriter<'_> {
+    type Error = ErrorKind;
+}
+
+/// Writes are partial when the writer is nearly full, and fail only when it
+/// is already full.
+impl Write for SliceWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        let len = cmp::min(self.remaining(), buf.len());
+        if len == 0 && !buf.is_empty() {
+            return Err(ErrorKind::WriteZero);
+        }
+        self.put_slice(&buf[..len]).unwrap();
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ErrorType for VecWriter {
+    type Error = Infallible;
+}
+
+#[cfg(feature = "alloc")]
+impl Write for VecWriter {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        self.put_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
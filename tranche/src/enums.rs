@@ -0,0 +1,212 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use core::convert::TryFrom;
+use core::mem;
+
+use crate::core::{BasedBufTranche, BufTranche, UnexpectedEndError};
+use crate::error::{InvalidDiscriminantError, TakeEnumError};
+
+/// Sets of flags which can be built out of their bits.
+///
+/// The method names match those of the types generated by the `bitflags`
+/// crate, so that implementing this trait for them is a matter of
+/// forwarding.
+///
+/// # Examples
+///
+/// ```
+/// # use tranche::{BufTranche, Flags};
+/// #[derive(Debug, PartialEq)]
+/// struct Perms(u8);
+///
+/// impl Flags for Perms {
+///     type Bits = u8;
+///
+///     fn from_bits(bits: u8) -> Option<Self> {
+///         if bits & !0b111 == 0 { Some(Perms(bits)) } else { None }
+///     }
+///
+///     fn from_bits_retain(bits: u8) -> Self {
+///         Perms(bits)
+///     }
+/// }
+///
+/// let mut tranche = BufTranche::new(&[0b101, 0b1001]);
+/// assert_eq!(tranche.take_flags::<Perms>(BufTranche::take_u8).unwrap(), Perms(0b101));
+/// assert!(tranche.take_flags::<Perms>(BufTranche::take_u8).is_err());
+/// assert_eq!(tranche.take_flags_retain::<Perms>(BufTranche::take_u8).unwrap(), Perms(0b1001));
+/// ```
+pub trait Flags: Sized {
+    /// The integer type of the bits.
+    type Bits: Copy;
+
+    /// Returns the flags with the given bits, or `None` if some of them
+    /// don't correspond to any flag.
+    fn from_bits(bits: Self::Bits) -> Option<Self>;
+
+    /// Returns the flags with the given bits, including those which don't
+    /// correspond to any flag.
+    fn from_bits_retain(bits: Self::Bits) -> Self;
+}
+
+impl<'a> BufTranche<'a> {
+    /// Takes an enum out of the tranche, given the taker of its
+    /// discriminant, which selects its width and endianness.
+    ///
+    /// Returns `Err(_)` with the raw discriminant if it does not convert to
+    /// `E`, or if `self` is not long enough, in which case the tranche is
+    /// left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use core::convert::TryFrom;
+    /// # use tranche::{BufTranche, TakeEnumError};
+    /// #[derive(Debug, PartialEq)]
+    /// enum Kind {
+    ///     Data,
+    ///     Ack,
+    /// }
+    ///
+    /// impl TryFrom<u16> for Kind {
+    ///     type Error = ();
+    ///
+    ///     fn try_from(value: u16) -> Result<Self, ()> {
+    ///         match value {
+    ///             1 => Ok(Kind::Data),
+    ///             2 => Ok(Kind::Ack),
+    ///             _ => Err(()),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let mut tranche = BufTranche::new(&[0, 2, 0, 3]);
+    /// assert_eq!(tranche.take_enum::<Kind, _>(BufTranche::take_u16_be).unwrap(), Kind::Ack);
+    /// match tranche.take_enum::<Kind, _>(BufTranche::take_u16_be).unwrap_err() {
+    ///     TakeEnumError::InvalidDiscriminant(err) => assert_eq!(*err.value(), 3),
+    ///     _ => unreachable!(),
+    /// }
+    /// assert_eq!(tranche.len(), 2);
+    /// ```
+    pub fn take_enum<E, R>(
+        &mut self,
+        take: impl FnOnce(&mut Self) -> Result<R, UnexpectedEndError>,
+    ) -> Result<E, TakeEnumError<R>>
+    where
+        E: TryFrom<R>,
+        R: Copy,
+    {
+        let mut rest = self.clone();
+        let raw = take(&mut rest)?;
+        let value = E::try_from(raw).map_err(|_| discriminant(raw))?;
+        *self = rest;
+        Ok(value)
+    }
+
+    /// Takes a set of flags out of the tranche, given the taker of its bits,
+    /// which selects their width and endianness.
+    ///
+    /// Returns `Err(_)` with the raw bits if some of them don't correspond
+    /// to any flag, or if `self` is not long enough, in which case the
+    /// tranche is left untouched.
+    pub fn take_flags<F>(
+        &mut self,
+        take: impl FnOnce(&mut Self) -> Result<F::Bits, UnexpectedEndError>,
+    ) -> Result<F, TakeEnumError<F::Bits>>
+    where
+        F: Flags,
+    {
+        let mut rest = self.clone();
+        let bits = take(&mut rest)?;
+        let flags = F::from_bits(bits).ok_or_else(|| discriminant(bits))?;
+        *self = rest;
+        Ok(flags)
+    }
+
+    /// Takes a set of flags out of the tranche, given the taker of its bits,
+    /// keeping the bits which don't correspond to any flag.
+    ///
+    /// Returns `Err(_)` if `self` is not long enough.
+    pub fn take_flags_retain<F>(
+        &mut self,
+        take: impl FnOnce(&mut Self) -> Result<F::Bits, UnexpectedEndError>,
+    ) -> Result<F, UnexpectedEndError>
+    where
+        F: Flags,
+    {
+        Ok(F::from_bits_retain(take(self)?))
+    }
+}
+
+impl<'a> BasedBufTranche<'a> {
+    /// Takes an enum out of the tranche, given the taker of its
+    /// discriminant.
+    ///
+    /// The internal offset is incremented accordingly.
+    ///
+    /// Returns `Err(_)` carrying the current offset if the discriminant does
+    /// not convert to `E` or if `self` is not long enough. See
+    /// [`BufTranche::take_enum`](type.BufTranche.html#method.take_enum).
+    pub fn take_enum<E, R>(
+        &mut self,
+        take: impl FnOnce(&mut Self) -> Result<R, UnexpectedEndError>,
+    ) -> Result<E, TakeEnumError<R>>
+    where
+        E: TryFrom<R>,
+        R: Copy,
+    {
+        let offset = self.offset();
+        let mut rest = self.clone();
+        let raw = take(&mut rest)?;
+        let value = E::try_from(raw).map_err(|_| discriminant(raw).at(offset))?;
+        *self = rest;
+        Ok(value)
+    }
+
+    /// Takes a set of flags out of the tranche, given the taker of its bits.
+    ///
+    /// The internal offset is incremented accordingly.
+    ///
+    /// Returns `Err(_)` carrying the current offset if some of the bits
+    /// don't correspond to any flag or if `self` is not long enough. See
+    /// [`BufTranche::take_flags`](type.BufTranche.html#method.take_flags).
+    pub fn take_flags<F>(
+        &mut self,
+        take: impl FnOnce(&mut Self) -> Result<F::Bits, UnexpectedEndError>,
+    ) -> Result<F, TakeEnumError<F::Bits>>
+    where
+        F: Flags,
+    {
+        let offset = self.offset();
+        let mut rest = self.clone();
+        let bits = take(&mut rest)?;
+        let flags = F::from_bits(bits).ok_or_else(|| discriminant(bits).at(offset))?;
+        *self = rest;
+        Ok(flags)
+    }
+
+    /// Takes a set of flags out of the tranche, given the taker of its bits,
+    /// keeping the bits which don't correspond to any flag.
+    ///
+    /// The internal offset is incremented accordingly.
+    ///
+    /// Returns `Err(_)` carrying the current offset if `self` is not long
+    /// enough.
+    pub fn take_flags_retain<F>(
+        &mut self,
+        take: impl FnOnce(&mut Self) -> Result<F::Bits, UnexpectedEndError>,
+    ) -> Result<F, UnexpectedEndError>
+    where
+        F: Flags,
+    {
+        Ok(F::from_bits_retain(take(self)?))
+    }
+}
+
+fn discriminant<R>(raw: R) -> InvalidDiscriminantError<R> {
+    InvalidDiscriminantError::new(raw, mem::size_of::<R>())
+}
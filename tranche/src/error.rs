@@ -0,0 +1,764 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::error;
+use core::fmt;
+use core::str::{self, Utf8Error};
+
+use crate::budget::Limit;
+use crate::core::{MisalignedError, OverflowError, TakeAsError, UnexpectedEndError};
+use crate::resume::ResumableError;
+
+/// An error from a decoder built on tranches.
+///
+/// All the errors returned by this crate convert into this type, so that
+/// decoders can use `?` on tranche operations and still report their own
+/// invalid values without inventing an error type.
+///
+/// Like the other enums of errors of this crate, it is transparent: both its
+/// message and its `source` are those of the error it wraps, so that error
+/// reporters walking the chain of sources don't print messages twice.
+///
+/// # Examples
+///
+/// ```
+/// # use tranche::{BufTranche, Error, InvalidValueError};
+/// fn version(t: &mut BufTranche) -> Result<u8, Error> {
+///     match t.take_u8()? {
+///         version @ 1..=3 => Ok(version),
+///         _ => Err(InvalidValueError::new("version").into()),
+///     }
+/// }
+///
+/// assert_eq!(version(&mut BufTranche::new(&[2])).unwrap(), 2);
+/// assert_eq!(
+///     version(&mut BufTranche::new(&[4])).unwrap_err().to_string(),
+///     "invalid version",
+/// );
+/// assert!(matches!(
+///     version(&mut BufTranche::new(&[])),
+///     Err(Error::UnexpectedEnd(_)),
+/// ));
+/// ```
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The end of the tranche was reached unexpectedly.
+    UnexpectedEnd(UnexpectedEndError),
+    /// A value was read successfully but is not valid.
+    InvalidValue(InvalidValueError),
+    /// A value did not fit in the integer type it had to be converted to.
+    Overflow(OverflowError),
+    /// Elements were left over after a complete parse.
+    TrailingData(TrailingDataError),
+    /// A tranche was not suitably aligned.
+    Misaligned(MisalignedError),
+    /// Bytes expected to be UTF-8 were not.
+    InvalidUtf8(Utf8Error),
+    /// A resource limit of a budget was exceeded.
+    LimitExceeded(LimitExceededError),
+    /// A required literal was not found.
+    Mismatch(MismatchError<Literal>),
+    /// An enum discriminant or a set of flags does not correspond to any
+    /// value.
+    InvalidDiscriminant(InvalidDiscriminantError<u64>),
+}
+
+impl From<UnexpectedEndError> for Error {
+    fn from(error: UnexpectedEndError) -> Self {
+        Error::UnexpectedEnd(error)
+    }
+}
+
+impl From<InvalidValueError> for Error {
+    fn from(error: InvalidValueError) -> Self {
+        Error::InvalidValue(error)
+    }
+}
+
+impl From<OverflowError> for Error {
+    fn from(error: OverflowError) -> Self {
+        Error::Overflow(error)
+    }
+}
+
+impl From<TrailingDataError> for Error {
+    fn from(error: TrailingDataError) -> Self {
+        Error::TrailingData(error)
+    }
+}
+
+impl From<MisalignedError> for Error {
+    fn from(error: MisalignedError) -> Self {
+        Error::Misaligned(error)
+    }
+}
+
+impl From<LimitExceededError> for Error {
+    fn from(error: LimitExceededError) -> Self {
+        Error::LimitExceeded(error)
+    }
+}
+
+impl From<Utf8Error> for Error {
+    fn from(error: Utf8Error) -> Self {
+        Error::InvalidUtf8(error)
+    }
+}
+
+impl<T> From<MismatchError<T>> for Error
+where
+    T: fmt::Debug,
+{
+    fn from(error: MismatchError<T>) -> Self {
+        Error::Mismatch(MismatchError {
+            expected: Literal::new(&error.expected),
+            found: Literal::new(&error.found),
+            len: error.len,
+            offset: error.offset,
+        })
+    }
+}
+
+impl<T> From<ExpectError<T>> for Error
+where
+    T: fmt::Debug,
+{
+    fn from(error: ExpectError<T>) -> Self {
+        match error {
+            ExpectError::UnexpectedEnd(error) => error.into(),
+            ExpectError::Mismatch(error) => error.into(),
+        }
+    }
+}
+
+impl From<TakeLenError> for Error {
+    fn from(error: TakeLenError) -> Self {
+        match error {
+            TakeLenError::UnexpectedEnd(error) => error.into(),
+            TakeLenError::Overflow(error) => error.into(),
+        }
+    }
+}
+
+impl<R> From<InvalidDiscriminantError<R>> for Error
+where
+    R: Into<u64>,
+{
+    fn from(error: InvalidDiscriminantError<R>) -> Self {
+        Error::InvalidDiscriminant(InvalidDiscriminantError {
+            value: error.value.into(),
+            len: error.len,
+            offset: error.offset,
+        })
+    }
+}
+
+impl<R> From<TakeEnumError<R>> for Error
+where
+    R: Into<u64>,
+{
+    fn from(error: TakeEnumError<R>) -> Self {
+        match error {
+            TakeEnumError::UnexpectedEnd(error) => error.into(),
+            TakeEnumError::InvalidDiscriminant(error) => error.into(),
+        }
+    }
+}
+
+impl From<TakeAsError> for Error {
+    fn from(error: TakeAsError) -> Self {
+        match error {
+            TakeAsError::UnexpectedEnd(error) => error.into(),
+            TakeAsError::Misaligned(error) => error.into(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::UnexpectedEnd(error) => error.fmt(fmt),
+            Error::InvalidValue(error) => error.fmt(fmt),
+            Error::Overflow(error) => error.fmt(fmt),
+            Error::TrailingData(error) => error.fmt(fmt),
+            Error::Misaligned(error) => error.fmt(fmt),
+            Error::InvalidUtf8(error) => error.fmt(fmt),
+            Error::LimitExceeded(error) => error.fmt(fmt),
+            Error::Mismatch(error) => error.fmt(fmt),
+            Error::InvalidDiscriminant(error) => error.fmt(fmt),
+        }
+    }
+}
+
+/// An error signalling that a value was read successfully but is not valid.
+#[derive(Clone, Debug)]
+pub struct InvalidValueError {
+    what: &'static str,
+}
+
+impl InvalidValueError {
+    /// Creates a new error signalling that the value described by `what` is
+    /// not valid.
+    pub fn new(what: &'static str) -> Self {
+        Self { what }
+    }
+
+    /// Returns the description of the value which is not valid.
+    pub fn what(&self) -> &'static str {
+        self.what
+    }
+}
+
+impl fmt::Display for InvalidValueError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "invalid {}", self.what)
+    }
+}
+
+/// An error signalling that a required literal was not found.
+///
+/// `T` is the type of the literal, which is a slice for the methods of
+/// tranches and an integer for the typed methods of tranches of bytes.
+#[derive(Clone, Debug)]
+pub struct MismatchError<T> {
+    expected: T,
+    found: T,
+    pub(crate) len: usize,
+    offset: Option<usize>,
+}
+
+impl<T> MismatchError<T> {
+    /// Creates a new error signalling that `found` was found instead of
+    /// `expected`, which spans `len` elements.
+    pub(crate) fn new(expected: T, found: T, len: usize) -> Self {
+        Self {
+            expected,
+            found,
+            len,
+            offset: None,
+        }
+    }
+
+    /// Records the offset at which the literal was expected.
+    pub(crate) fn at(self, offset: usize) -> Self {
+        Self {
+            offset: Some(offset),
+            ..self
+        }
+    }
+
+    /// Returns the literal which was expected.
+    pub fn expected(&self) -> &T {
+        &self.expected
+    }
+
+    /// Returns what was found instead.
+    ///
+    /// For slices, this may be shorter than what was expected if the end of
+    /// the tranche was reached.
+    pub fn found(&self) -> &T {
+        &self.found
+    }
+
+    /// Returns the offset at which the literal was expected, if it was
+    /// expected in a based tranche.
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+}
+
+impl<T> fmt::Display for MismatchError<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str("mismatch")?;
+        if let Some(offset) = self.offset {
+            write!(fmt, " at offset {:#x}", offset)?;
+        }
+        write!(
+            fmt,
+            " (expected {:x?}, found {:x?})",
+            self.expected, self.found,
+        )
+    }
+}
+
+/// The rendering of a literal kept by [`Error`](enum.Error.html) once the
+/// type of the literal is erased.
+///
+/// It is the hexadecimal `Debug` rendering of the literal, truncated if
+/// needed. With the `alloc` feature, it is stored on the heap and truncated
+/// to 64 bytes. Without it, it is stored inline and truncated to 16 bytes,
+/// which is enough for any integer up to 64 bits, so that `Error` stays
+/// within 64 bytes.
+#[derive(Clone)]
+pub struct Literal {
+    #[cfg(feature = "alloc")]
+    text: Box<str>,
+    #[cfg(not(feature = "alloc"))]
+    text: Rendering,
+    truncated: bool,
+}
+
+#[cfg(feature = "alloc")]
+const LITERAL_CAPACITY: usize = 64;
+#[cfg(not(feature = "alloc"))]
+const LITERAL_CAPACITY: usize = 16;
+
+/// A rendering truncated to `LITERAL_CAPACITY` bytes.
+#[derive(Clone)]
+struct Rendering {
+    buf: [u8; LITERAL_CAPACITY],
+    len: u8,
+    truncated: bool,
+}
+
+impl Literal {
+    fn new(value: &impl fmt::Debug) -> Self {
+        let mut rendering = Rendering {
+            buf: [0; LITERAL_CAPACITY],
+            len: 0,
+            truncated: false,
+        };
+        let _ = fmt::write(&mut rendering, format_args!("{:x?}", value));
+        Self {
+            truncated: rendering.truncated,
+            #[cfg(feature = "alloc")]
+            text: rendering.as_str().into(),
+            #[cfg(not(feature = "alloc"))]
+            text: rendering,
+        }
+    }
+
+    /// Returns the rendering of the literal.
+    pub fn as_str(&self) -> &str {
+        #[cfg(feature = "alloc")]
+        return &self.text;
+        #[cfg(not(feature = "alloc"))]
+        return self.text.as_str();
+    }
+
+    /// Returns whether the rendering of the literal was truncated.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+}
+
+impl Rendering {
+    fn as_str(&self) -> &str {
+        str::from_utf8(&self.buf[..self.len as usize]).unwrap()
+    }
+}
+
+impl fmt::Write for Rendering {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let len = self.len as usize;
+        let mut n = s.len().min(LITERAL_CAPACITY - len);
+        while !s.is_char_boundary(n) {
+            n -= 1;
+        }
+        self.buf[len..len + n].copy_from_slice(&s.as_bytes()[..n]);
+        self.len += n as u8;
+        if n < s.len() {
+            self.truncated = true;
+            return Err(fmt::Error);
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Literal {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(self.as_str())?;
+        if self.truncated {
+            fmt.write_str("..")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Literal {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, fmt)
+    }
+}
+
+/// An error signalling that a required literal could not be consumed.
+#[derive(Clone, Debug)]
+pub enum ExpectError<T> {
+    /// The end of the tranche was reached unexpectedly.
+    UnexpectedEnd(UnexpectedEndError),
+    /// Something else than the literal was found.
+    Mismatch(MismatchError<T>),
+}
+
+impl<T> ExpectError<T> {
+    /// Records the offset at which the literal was expected.
+    pub(crate) fn at(self, offset: usize) -> Self {
+        match self {
+            ExpectError::UnexpectedEnd(error) => ExpectError::UnexpectedEnd(error.at(offset)),
+            ExpectError::Mismatch(error) => ExpectError::Mismatch(error.at(offset)),
+        }
+    }
+}
+
+impl<T> From<UnexpectedEndError> for ExpectError<T> {
+    fn from(error: UnexpectedEndError) -> Self {
+        ExpectError::UnexpectedEnd(error)
+    }
+}
+
+impl<T> From<MismatchError<T>> for ExpectError<T> {
+    fn from(error: MismatchError<T>) -> Self {
+        ExpectError::Mismatch(error)
+    }
+}
+
+impl<T> fmt::Display for ExpectError<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExpectError::UnexpectedEnd(error) => error.fmt(fmt),
+            ExpectError::Mismatch(error) => error.fmt(fmt),
+        }
+    }
+}
+
+/// An error signalling that an enum discriminant or a set of flags read
+/// successfully does not correspond to any value.
+#[derive(Clone, Debug)]
+pub struct InvalidDiscriminantError<R> {
+    value: R,
+    pub(crate) len: usize,
+    pub(crate) offset: Option<usize>,
+}
+
+impl<R> InvalidDiscriminantError<R> {
+    /// Creates a new error signalling that the raw `value`, which spans `len`
+    /// bytes, is not valid.
+    pub(crate) fn new(value: R, len: usize) -> Self {
+        Self {
+            value,
+            len,
+            offset: None,
+        }
+    }
+
+    /// Records the offset at which the raw value starts.
+    pub(crate) fn at(self, offset: usize) -> Self {
+        Self {
+            offset: Some(offset),
+            ..self
+        }
+    }
+
+    /// Returns the raw value.
+    pub fn value(&self) -> &R {
+        &self.value
+    }
+
+    /// Returns the offset at which the raw value starts, if it was read from
+    /// a based tranche.
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+}
+
+impl<R> fmt::Display for InvalidDiscriminantError<R>
+where
+    R: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "invalid discriminant {:#x?}", self.value)?;
+        if let Some(offset) = self.offset {
+            write!(fmt, " at offset {:#x}", offset)?;
+        }
+        Ok(())
+    }
+}
+
+/// An error signalling that an enum or a set of flags could not be taken out
+/// of a tranche.
+///
+/// It converts into [`Error`](enum.Error.html), keeping the raw value, when
+/// `R` converts into `u64`.
+#[derive(Clone, Debug)]
+pub enum TakeEnumError<R> {
+    /// The end of the tranche was reached unexpectedly.
+    UnexpectedEnd(UnexpectedEndError),
+    /// The raw value does not correspond to any value.
+    InvalidDiscriminant(InvalidDiscriminantError<R>),
+}
+
+impl<R> From<UnexpectedEndError> for TakeEnumError<R> {
+    fn from(error: UnexpectedEndError) -> Self {
+        TakeEnumError::UnexpectedEnd(error)
+    }
+}
+
+impl<R> From<InvalidDiscriminantError<R>> for TakeEnumError<R> {
+    fn from(error: InvalidDiscriminantError<R>) -> Self {
+        TakeEnumError::InvalidDiscriminant(error)
+    }
+}
+
+impl<R> fmt::Display for TakeEnumError<R>
+where
+    R: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TakeEnumError::UnexpectedEnd(error) => error.fmt(fmt),
+            TakeEnumError::InvalidDiscriminant(error) => error.fmt(fmt),
+        }
+    }
+}
+
+/// An error signalling that a resource limit of a budget was exceeded.
+#[derive(Clone, Debug)]
+pub struct LimitExceededError {
+    limit: Limit,
+    max: usize,
+}
+
+impl LimitExceededError {
+    pub(crate) fn new(limit: Limit, max: usize) -> Self {
+        Self { limit, max }
+    }
+
+    /// Returns which limit was exceeded.
+    pub fn limit(&self) -> Limit {
+        self.limit
+    }
+
+    /// Returns the value of the limit which was exceeded.
+    pub fn max(&self) -> usize {
+        self.max
+    }
+}
+
+impl fmt::Display for LimitExceededError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{} limit of {} exceeded", self.limit, self.max)
+    }
+}
+
+/// An error signalling that a length could not be taken out of a tranche.
+#[derive(Clone, Debug)]
+pub enum TakeLenError {
+    /// The end of the tranche was reached unexpectedly.
+    UnexpectedEnd(UnexpectedEndError),
+    /// The length does not fit in a `usize`.
+    Overflow(OverflowError),
+}
+
+impl TakeLenError {
+    /// Records the offset at which the `len` elements the length was read
+    /// from start.
+    pub(crate) fn at(self, offset: usize, len: usize) -> Self {
+        match self {
+            TakeLenError::UnexpectedEnd(error) => TakeLenError::UnexpectedEnd(error.at(offset)),
+            TakeLenError::Overflow(error) => {
+                TakeLenError::Overflow(error.spanning(offset..offset.saturating_add(len)))
+            }
+        }
+    }
+}
+
+impl From<UnexpectedEndError> for TakeLenError {
+    fn from(error: UnexpectedEndError) -> Self {
+        TakeLenError::UnexpectedEnd(error)
+    }
+}
+
+impl From<OverflowError> for TakeLenError {
+    fn from(error: OverflowError) -> Self {
+        TakeLenError::Overflow(error)
+    }
+}
+
+impl fmt::Display for TakeLenError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TakeLenError::UnexpectedEnd(error) => error.fmt(fmt),
+            TakeLenError::Overflow(error) => error.fmt(fmt),
+        }
+    }
+}
+
+/// An error signalling that elements were left over after a complete parse.
+#[derive(Clone, Debug)]
+pub struct TrailingDataError {
+    len: usize,
+    offset: Option<usize>,
+}
+
+#[allow(clippy::len_without_is_empty)]
+impl TrailingDataError {
+    /// Creates a new error signalling that `len` elements were left over.
+    pub(crate) fn new(len: usize) -> Self {
+        Self { len, offset: None }
+    }
+
+    /// Records the offset at which the elements left over start.
+    pub(crate) fn at(self, offset: usize) -> Self {
+        Self {
+            offset: Some(offset),
+            ..self
+        }
+    }
+
+    /// Returns the number of elements which were left over.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the offset at which the elements left over start, if they
+    /// were left over in a based tranche.
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+}
+
+impl fmt::Display for TrailingDataError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str("trailing data")?;
+        if let Some(offset) = self.offset {
+            write!(fmt, " at offset {:#x}", offset)?;
+        }
+        write!(fmt, " ({} left)", self.len)
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::UnexpectedEnd(error) => error::Error::source(error),
+            Error::InvalidValue(error) => error::Error::source(error),
+            Error::Overflow(error) => error::Error::source(error),
+            Error::TrailingData(error) => error::Error::source(error),
+            Error::Misaligned(error) => error::Error::source(error),
+            Error::InvalidUtf8(error) => error::Error::source(error),
+            Error::LimitExceeded(error) => error::Error::source(error),
+            Error::Mismatch(error) => error::Error::source(error),
+            Error::InvalidDiscriminant(error) => error::Error::source(error),
+        }
+    }
+}
+
+impl error::Error for UnexpectedEndError {
+    fn description(&self) -> &str {
+        "unexpected end"
+    }
+}
+
+impl error::Error for InvalidValueError {
+    fn description(&self) -> &str {
+        "invalid value"
+    }
+}
+
+impl error::Error for OverflowError {
+    fn description(&self) -> &str {
+        "integer overflow"
+    }
+}
+
+impl error::Error for TrailingDataError {
+    fn description(&self) -> &str {
+        "trailing data"
+    }
+}
+
+impl error::Error for MisalignedError {
+    fn description(&self) -> &str {
+        "misaligned"
+    }
+}
+
+impl<T> error::Error for MismatchError<T>
+where
+    T: fmt::Debug,
+{
+    fn description(&self) -> &str {
+        "mismatch"
+    }
+}
+
+impl<T> error::Error for ExpectError<T>
+where
+    T: fmt::Debug,
+{
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            ExpectError::UnexpectedEnd(error) => error::Error::source(error),
+            ExpectError::Mismatch(error) => error::Error::source(error),
+        }
+    }
+}
+
+impl<R> error::Error for InvalidDiscriminantError<R>
+where
+    R: fmt::Debug,
+{
+    fn description(&self) -> &str {
+        "invalid discriminant"
+    }
+}
+
+impl<R> error::Error for TakeEnumError<R>
+where
+    R: fmt::Debug,
+{
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            TakeEnumError::UnexpectedEnd(error) => error::Error::source(error),
+            TakeEnumError::InvalidDiscriminant(error) => error::Error::source(error),
+        }
+    }
+}
+
+impl error::Error for LimitExceededError {
+    fn description(&self) -> &str {
+        "limit exceeded"
+    }
+}
+
+impl error::Error for TakeLenError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            TakeLenError::UnexpectedEnd(error) => error::Error::source(error),
+            TakeLenError::Overflow(error) => error::Error::source(error),
+        }
+    }
+}
+
+impl error::Error for TakeAsError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            TakeAsError::UnexpectedEnd(error) => error::Error::source(error),
+            TakeAsError::Misaligned(error) => error::Error::source(error),
+        }
+    }
+}
+
+impl<E> error::Error for ResumableError<E>
+where
+    E: error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            ResumableError::Incomplete { .. } => None,
+            ResumableError::Malformed(error) => error::Error::source(error),
+        }
+    }
+}
@@ -0,0 +1,82 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use std::io::{self, BufRead, Read, Write};
+
+use futures_io::{AsyncBufRead, AsyncRead, AsyncWrite};
+
+use crate::{BasedBufTranche, BufTranche, SliceWriter, VecWriter};
+
+macro_rules! impl_async_read {
+    ($ty:ty) => {
+        impl AsyncRead for $ty {
+            fn poll_read(
+                self: Pin<&mut Self>,
+                _: &mut Context<'_>,
+                buf: &mut [u8],
+            ) -> Poll<io::Result<usize>> {
+                Poll::Ready(self.get_mut().read(buf))
+            }
+
+            fn poll_read_vectored(
+                self: Pin<&mut Self>,
+                _: &mut Context<'_>,
+                bufs: &mut [io::IoSliceMut<'_>],
+            ) -> Poll<io::Result<usize>> {
+                Poll::Ready(self.get_mut().read_vectored(bufs))
+            }
+        }
+
+        impl AsyncBufRead for $ty {
+            fn poll_fill_buf(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+                Poll::Ready(Ok(self.get_mut().as_slice()))
+            }
+
+            fn consume(self: Pin<&mut Self>, amt: usize) {
+                BufRead::consume(self.get_mut(), amt)
+            }
+        }
+    };
+}
+
+impl_async_read!(BufTranche<'_>);
+impl_async_read!(BasedBufTranche<'_>);
+
+macro_rules! impl_async_write {
+    ($ty:ty) => {
+        impl AsyncWrite for $ty {
+            fn poll_write(
+                self: Pin<&mut Self>,
+                _: &mut Context<'_>,
+                buf: &[u8],
+            ) -> Poll<io::Result<usize>> {
+                Poll::Ready(self.get_mut().write(buf))
+            }
+
+            fn poll_write_vectored(
+                self: Pin<&mut Self>,
+                _: &mut Context<'_>,
+                bufs: &[io::IoSlice<'_>],
+            ) -> Poll<io::Result<usize>> {
+                Poll::Ready(self.get_mut().write_vectored(bufs))
+            }
+
+            fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+                Poll::Ready(Ok(()))
+            }
+
+            fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+                Poll::Ready(Ok(()))
+            }
+        }
+    };
+}
+
+impl_async_write!(SliceWriter<'_>);
+impl_async_write!(VecWriter);
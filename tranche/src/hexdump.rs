@@ -0,0 +1,230 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use core::cmp;
+use core::fmt;
+use core::ops::Range;
+
+use crate::context::ContextError;
+use crate::core::{OverflowError, UnexpectedEndError};
+use crate::error::{
+    Error, ExpectError, InvalidDiscriminantError, MismatchError, TakeEnumError, TakeLenError,
+    TrailingDataError,
+};
+
+/// Errors which may know which bytes of the input they are about.
+pub trait ErrorSpan {
+    /// Returns the range of offsets the error is about, relative to the start
+    /// of the original based tranche, if known.
+    ///
+    /// The range may extend past the end of the input.
+    fn span(&self) -> Option<Range<usize>>;
+}
+
+impl ErrorSpan for UnexpectedEndError {
+    fn span(&self) -> Option<Range<usize>> {
+        let offset = self.offset()?;
+        Some(offset..offset.saturating_add(self.needed()))
+    }
+}
+
+impl ErrorSpan for TrailingDataError {
+    fn span(&self) -> Option<Range<usize>> {
+        let offset = self.offset()?;
+        Some(offset..offset.saturating_add(self.len()))
+    }
+}
+
+impl ErrorSpan for OverflowError {
+    fn span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+}
+
+impl ErrorSpan for TakeLenError {
+    fn span(&self) -> Option<Range<usize>> {
+        match self {
+            TakeLenError::UnexpectedEnd(error) => error.span(),
+            TakeLenError::Overflow(error) => error.span(),
+        }
+    }
+}
+
+impl<R> ErrorSpan for InvalidDiscriminantError<R> {
+    fn span(&self) -> Option<Range<usize>> {
+        let offset = self.offset()?;
+        Some(offset..offset.saturating_add(self.len))
+    }
+}
+
+impl<R> ErrorSpan for TakeEnumError<R> {
+    fn span(&self) -> Option<Range<usize>> {
+        match self {
+            TakeEnumError::UnexpectedEnd(error) => error.span(),
+            TakeEnumError::InvalidDiscriminant(error) => error.span(),
+        }
+    }
+}
+
+impl<T> ErrorSpan for MismatchError<T> {
+    fn span(&self) -> Option<Range<usize>> {
+        let offset = self.offset()?;
+        Some(offset..offset.saturating_add(self.len))
+    }
+}
+
+impl<T> ErrorSpan for ExpectError<T> {
+    fn span(&self) -> Option<Range<usize>> {
+        match self {
+            ExpectError::UnexpectedEnd(error) => error.span(),
+            ExpectError::Mismatch(error) => error.span(),
+        }
+    }
+}
+
+impl ErrorSpan for Error {
+    fn span(&self) -> Option<Range<usize>> {
+        match self {
+            Error::UnexpectedEnd(error) => error.span(),
+            Error::Overflow(error) => error.span(),
+            Error::TrailingData(error) => error.span(),
+            Error::Mismatch(error) => error.span(),
+            Error::InvalidDiscriminant(error) => error.span(),
+            _ => None,
+        }
+    }
+}
+
+impl ErrorSpan for ContextError {
+    fn span(&self) -> Option<Range<usize>> {
+        self.error().span()
+    }
+}
+
+/// Renders an error along with a hexdump of the bytes it is about.
+///
+/// The bytes the error is about are highlighted with carets, and the number
+/// of bytes past the end of the input that an unexpected end needed is
+/// reported on a last line. Only the error message is rendered if its span
+/// is unknown.
+///
+/// # Examples
+///
+/// ```
+/// # use tranche::{BasedBufTranche, BufTranche, Hexdump};
+/// let buf = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0";
+/// let mut tranche = BasedBufTranche::from(BufTranche::new(buf));
+/// tranche.take_front(16).unwrap();
+/// let err = tranche.take_u32_be().unwrap_err();
+///
+/// let report = Hexdump::new(buf, &err).with_context(4).to_string();
+/// assert_eq!(
+///     report,
+///     "error: unexpected end at offset 0x10 (needed 4, got 2)\n\
+///      00000000  89 50 4e 47 0d 0a 1a 0a 00 00 00 0d 49 48 44 52  |.PNG........IHDR|\n\
+///      00000010  00 00                                            |..|\n\
+///      \x20         ^^ ^^\n\
+///      \x20         ... needed 2 more bytes past end of input",
+/// );
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Hexdump<'a, E> {
+    buf: &'a [u8],
+    error: &'a E,
+    context: usize,
+    row_width: usize,
+}
+
+impl<'a, E> Hexdump<'a, E> {
+    /// Creates a new hexdump of `buf`, which must be the input whose start
+    /// the offsets of `error` are relative to.
+    ///
+    /// Defaults to 16 bytes of context on each side of the span, and 16
+    /// bytes per row.
+    pub fn new(buf: &'a [u8], error: &'a E) -> Self {
+        Self {
+            buf,
+            error,
+            context: 16,
+            row_width: 16,
+        }
+    }
+
+    /// Sets how many bytes to show before and after the span of the error,
+    /// which is rounded to whole rows.
+    pub fn with_context(self, context: usize) -> Self {
+        Self { context, ..self }
+    }
+
+    /// Sets how many bytes to show per row.
+    ///
+    /// Panics if `row_width` is 0.
+    pub fn with_row_width(self, row_width: usize) -> Self {
+        assert!(row_width > 0, "row width must not be 0");
+        Self { row_width, ..self }
+    }
+}
+
+impl<E> fmt::Display for Hexdump<'_, E>
+where
+    E: ErrorSpan + fmt::Display,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "error: {}", self.error)?;
+        let span = match self.error.span() {
+            Some(span) => span,
+            None => return Ok(()),
+        };
+        let width = self.row_width;
+        let len = self.buf.len();
+        let start = cmp::min(span.start, len).saturating_sub(self.context) / width * width;
+        let end = cmp::min(span.end, len).saturating_add(self.context);
+        let end = cmp::min(end, len);
+        let mut row = start;
+        while row < end {
+            let row_end = row.saturating_add(width);
+            write!(fmt, "\n{:08x} ", row)?;
+            for offset in row..row_end {
+                match self.buf.get(offset) {
+                    Some(byte) => write!(fmt, " {:02x}", byte)?,
+                    None => fmt.write_str("   ")?,
+                }
+            }
+            fmt.write_str("  |")?;
+            for &byte in &self.buf[row..cmp::min(row_end, len)] {
+                let c = if byte.is_ascii_graphic() || byte == b' ' {
+                    byte as char
+                } else {
+                    '.'
+                };
+                write!(fmt, "{}", c)?;
+            }
+            fmt.write_str("|")?;
+            let highlighted = cmp::max(span.start, row)..cmp::min(span.end, cmp::min(row_end, len));
+            if !highlighted.is_empty() {
+                fmt.write_str("\n         ")?;
+                for offset in row..highlighted.end {
+                    let cell = if offset < highlighted.start {
+                        "   "
+                    } else {
+                        " ^^"
+                    };
+                    fmt.write_str(cell)?;
+                }
+            }
+            row = row_end;
+        }
+        if span.end > len {
+            let missing = span.end - cmp::max(span.start, len);
+            write!(
+                fmt,
+                "\n          ... needed {} more bytes past end of input",
+                missing,
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -7,6 +7,8 @@
 use core::iter::FusedIterator;
 
 use crate::core::{BasedTranche, Tranche};
+#[cfg(feature = "alloc")]
+use crate::vec::VecTranche;
 
 impl<'a, T> Iterator for Tranche<'a, T> {
     type Item = &'a T;
@@ -56,3 +58,37 @@ impl<T> ExactSizeIterator for BasedTranche<'_, T> {
 impl<T> FusedIterator for Tranche<'_, T> {}
 
 impl<T> FusedIterator for BasedTranche<'_, T> {}
+
+#[cfg(feature = "alloc")]
+impl<T> Iterator for VecTranche<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    fn count(self) -> usize {
+        self.inner.count()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> DoubleEndedIterator for VecTranche<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> ExactSizeIterator for VecTranche<T> {
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> FusedIterator for VecTranche<T> {}
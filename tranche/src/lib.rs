@@ -6,31 +6,189 @@
 
 //! # Tranche
 //!
-//! See [`Tranche<'_, T>`](struct.Tranche.html) for what this crate does.
+//! See [`Tranche<'_, T>`](struct.Tranche.html) for what this crate does, and
+//! [`SliceWriter<'_>`](struct.SliceWriter.html) for its writing counterpart.
+//! Bytes spread over several segments can be consumed with
+//! [`ChainedBufTranche<'_>`](struct.ChainedBufTranche.html), and parsers of
+//! input received incrementally can tell incomplete input from malformed
+//! input with [`ResumableError<E>`](enum.ResumableError.html).
 //!
-//! This crate is `no_std` by default, the `std` feature provides:
+//! Every error type of this crate implements `core::error::Error` and
+//! converts into [`Error`](enum.Error.html), which decoders can use instead
+//! of inventing their own error type. Errors can be labelled with the part of
+//! the input being parsed with [`Context`](trait.Context.html), and rendered
+//! along with the bytes they are about with [`Hexdump`](struct.Hexdump.html).
+//! Parsers of untrusted input can bound the resources they use with
+//! [`Budget<T>`](struct.Budget.html).
 //!
-//! * an implementation of `std::error::Error` for
-//!   [`UnexpectedEndError`](struct.UnexpectedEndError.html);
+//! This crate is `no_std` by default, the `alloc` feature provides:
+//!
+//! * [`VecWriter`](struct.VecWriter.html), a growable counterpart to
+//!   `SliceWriter<'_>`;
+//! * [`SharedBufTranche`](struct.SharedBufTranche.html), an owned and cheaply
+//!   clonable counterpart to `BufTranche<'_>`;
+//! * [`OwnedBufTranche`](struct.OwnedBufTranche.html), an owned buffer handing
+//!   out `BasedBufTranche<'_>` values borrowing from itself;
+//! * [`VecTranche<T>`](struct.VecTranche.html), an owning counterpart to
+//!   `Tranche<'_, T>` moving its elements out;
+//! * [`FrameBuffer`](struct.FrameBuffer.html), which drives resumable parsers
+//!   over input received incrementally.
+//!
+//! The `std` feature implies `alloc` and provides:
+//!
+//! * [`StreamTranche<R>`](struct.StreamTranche.html), a tranche of bytes
+//!   refilled from a `std::io::Read` value;
 //! * an implementation of `std::io::Read` and `std::io::BufRead` for
 //!   [`BufTranche<'_>`](type.BufTranche.html) and
-//!   [`BasedBufTranche<'_>`](type.BasedBufTranche.html);
-//! * an implementation of `From<UnexpectedEndError>` for `std::io::Error`.
+//!   [`BasedBufTranche<'_>`](type.BasedBufTranche.html), and of
+//!   `std::io::Seek` for the latter;
+//! * an implementation of `std::io::Write` for
+//!   [`SliceWriter<'_>`](struct.SliceWriter.html) and
+//!   [`VecWriter`](struct.VecWriter.html);
+//! * an implementation of `From<UnexpectedEndError>`, `From<TakeAsError>`,
+//!   `From<TakeLenError>`, `From<TrailingDataError>`, `From<ExpectError<T>>`,
+//!   `From<TakeEnumError<R>>`, `From<Error>` and `From<ContextError>` for
+//!   `std::io::Error`.
+//!
+//! The `bytes` feature provides an implementation of `bytes::Buf` for
+//! [`BufTranche<'_>`](type.BufTranche.html) and
+//! [`BasedBufTranche<'_>`](type.BasedBufTranche.html), and of `From<&Bytes>`
+//! for both of them.
+//!
+//! The `embedded-io` feature doesn't require `std` and provides an
+//! implementation of `embedded_io::Read` and `embedded_io::BufRead` for
+//! [`BufTranche<'_>`](type.BufTranche.html) and
+//! [`BasedBufTranche<'_>`](type.BasedBufTranche.html), of
+//! `embedded_io::Write` for [`SliceWriter<'_>`](struct.SliceWriter.html) and
+//! [`VecWriter`](struct.VecWriter.html), and of `embedded_io::Error` for
+//! [`UnexpectedEndError`](struct.UnexpectedEndError.html).
+//!
+//! The `futures-io` feature implies `std` and provides an implementation of
+//! `futures_io::AsyncRead` and `futures_io::AsyncBufRead` for
+//! [`BufTranche<'_>`](type.BufTranche.html) and
+//! [`BasedBufTranche<'_>`](type.BasedBufTranche.html), and of
+//! `futures_io::AsyncWrite` for [`SliceWriter<'_>`](struct.SliceWriter.html)
+//! and [`VecWriter`](struct.VecWriter.html). These are always ready.
+//!
+//! The `tokio` feature implies `std` and provides
+//! [`TrancheDecoder<F>`](struct.TrancheDecoder.html), which turns a tranche
+//! parser into a `tokio_util::codec::Decoder`, as well as an implementation
+//! of `tokio::io::AsyncRead` and `tokio::io::AsyncBufRead` for
+//! [`BufTranche<'_>`](type.BufTranche.html) and
+//! [`BasedBufTranche<'_>`](type.BasedBufTranche.html).
+//!
+//! The `mmap` feature implies `std` and provides
+//! [`MappedBufTranche`](struct.MappedBufTranche.html), the bytes of a file
+//! either memory-mapped or read in memory.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![deny(unsafe_code)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 #[allow(unsafe_code)]
 mod core;
 
+#[macro_use]
 #[allow(unsafe_code)]
 mod buf;
 
+#[forbid(unsafe_code)]
+mod budget;
+
+#[cfg(feature = "bytes")]
+#[forbid(unsafe_code)]
+mod bytes;
+
+#[forbid(unsafe_code)]
+mod chain;
+
+#[forbid(unsafe_code)]
+mod context;
+
+#[cfg(feature = "embedded-io")]
+#[forbid(unsafe_code)]
+mod embedded_io;
+
+#[forbid(unsafe_code)]
+mod enums;
+
+#[forbid(unsafe_code)]
+mod error;
+
+#[cfg(feature = "futures-io")]
+#[forbid(unsafe_code)]
+mod futures_io;
+
+#[forbid(unsafe_code)]
+mod hexdump;
+
 #[forbid(unsafe_code)]
 mod iter;
 
+#[cfg(feature = "mmap")]
+#[allow(unsafe_code)]
+mod mmap;
+
+#[cfg(feature = "alloc")]
+#[forbid(unsafe_code)]
+mod owned;
+
+#[forbid(unsafe_code)]
+mod resume;
+
+#[cfg(feature = "alloc")]
+#[forbid(unsafe_code)]
+mod shared;
+
 #[cfg(feature = "std")]
 #[forbid(unsafe_code)]
 mod std;
 
-pub use self::core::{BasedBufTranche, BasedTranche, BufTranche, Tranche, UnexpectedEndError};
+#[cfg(feature = "std")]
+#[forbid(unsafe_code)]
+mod stream;
+
+#[cfg(feature = "tokio")]
+#[forbid(unsafe_code)]
+mod tokio;
+
+#[cfg(feature = "alloc")]
+#[forbid(unsafe_code)]
+mod vec;
+
+#[forbid(unsafe_code)]
+mod writer;
+
+pub use self::budget::{Budget, Limit};
+pub use self::chain::{ChainedBufTranche, ChainedFront};
+pub use self::context::{Context, ContextError, ContextLabel};
+pub use self::core::{
+    BasedBufTranche, BasedTranche, BufTranche, MisalignedError, OverflowError, TakeAsError,
+    Tranche, UnexpectedEndError,
+};
+pub use self::enums::Flags;
+pub use self::error::{
+    Error, ExpectError, InvalidDiscriminantError, InvalidValueError, LimitExceededError, Literal,
+    MismatchError, TakeEnumError, TakeLenError, TrailingDataError,
+};
+pub use self::hexdump::{ErrorSpan, Hexdump};
+#[cfg(feature = "mmap")]
+pub use self::mmap::MappedBufTranche;
+#[cfg(feature = "alloc")]
+pub use self::owned::OwnedBufTranche;
+#[cfg(feature = "alloc")]
+pub use self::resume::FrameBuffer;
+pub use self::resume::ResumableError;
+#[cfg(feature = "alloc")]
+pub use self::shared::SharedBufTranche;
+#[cfg(feature = "std")]
+pub use self::stream::StreamTranche;
+#[cfg(feature = "tokio")]
+pub use self::tokio::TrancheDecoder;
+#[cfg(feature = "alloc")]
+pub use self::vec::VecTranche;
+pub use self::writer::SliceWriter;
+#[cfg(feature = "alloc")]
+pub use self::writer::{LenPlaceholder, LenPrefix, VecWriter};
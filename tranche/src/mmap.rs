@@ -0,0 +1,210 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use core::convert::TryFrom;
+use core::fmt;
+
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+
+use memmap2::{Mmap, MmapOptions};
+
+use crate::core::{BasedBufTranche, BufTranche};
+
+/// The bytes of a file, either memory-mapped or copied in memory.
+///
+/// Mapped tranches hand out based tranches borrowing from themselves, whose
+/// offsets are relative to the start of what was mapped. Files larger than
+/// the address space, which can happen on 32-bit targets, can be mapped one
+/// range at a time with `map_range`, in which case `absolute_offset` converts
+/// offsets back to positions in the whole file.
+///
+/// Mapping a file is unsafe, because nothing prevents other processes from
+/// modifying or truncating it while it is mapped. Doing so is undefined
+/// behaviour, which can show up as a crash. The `read` and `read_range`
+/// constructors copy the bytes in memory instead.
+///
+/// # Examples
+///
+/// ```
+/// # use tranche::MappedBufTranche;
+/// # let path = std::env::temp_dir().join(format!("tranche-mapped-doctest-{}", std::process::id()));
+/// std::fs::write(&path, [0xca, 0xfe, 0, 1])?;
+///
+/// // Safety: nothing else modifies the file while it is mapped.
+/// let mapped = unsafe { MappedBufTranche::open(&path)? };
+/// let mut tranche = mapped.as_based_tranche();
+/// assert_eq!(tranche.take_u16_be()?, 0xcafe);
+/// assert_eq!(mapped.absolute_offset(tranche.offset()), 2);
+/// # std::fs::remove_file(&path)?;
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub struct MappedBufTranche {
+    bytes: Bytes,
+    base: u64,
+}
+
+enum Bytes {
+    Mapped(Mmap),
+    Copied(Box<[u8]>),
+}
+
+impl MappedBufTranche {
+    /// Opens the file at the given path and maps it whole in memory.
+    ///
+    /// Returns `Err(_)` if the file cannot be opened or mapped, or with
+    /// `std::io::ErrorKind::InvalidInput` if it is larger than the address
+    /// space, see `map_range`.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated while it is mapped.
+    pub unsafe fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::map(&File::open(path)?)
+    }
+
+    /// Maps the given file whole in memory.
+    ///
+    /// Returns `Err(_)` as described in `open`.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated while it is mapped.
+    pub unsafe fn map(file: &File) -> io::Result<Self> {
+        let len = whole_len(file)?;
+        Self::map_range(file, 0, len)
+    }
+
+    /// Maps `len` bytes of the given file in memory, starting at `offset`.
+    ///
+    /// Returns `Err(_)` if the range cannot be mapped, notably if it goes
+    /// past the end of the file.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated while it is mapped.
+    pub unsafe fn map_range(file: &File, offset: u64, len: usize) -> io::Result<Self> {
+        check_range(file, offset, len)?;
+        let bytes = if len == 0 {
+            Bytes::Copied(Box::default())
+        } else {
+            Bytes::Mapped(MmapOptions::new().offset(offset).len(len).map(file)?)
+        };
+        Ok(Self {
+            bytes,
+            base: offset,
+        })
+    }
+
+    /// Reads the whole file at the given path in memory, as a safe fallback
+    /// to `open`.
+    ///
+    /// Returns `Err(_)` if the file cannot be read, or with
+    /// `std::io::ErrorKind::InvalidInput` if it is larger than the address
+    /// space.
+    pub fn read(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut file = File::open(path)?;
+        let len = whole_len(&file)?;
+        Self::read_range(&mut file, 0, len)
+    }
+
+    /// Reads `len` bytes of the given file in memory, starting at `offset`,
+    /// as a safe fallback to `map_range`.
+    ///
+    /// Returns `Err(_)` if the range cannot be read, notably if it goes past
+    /// the end of the file.
+    pub fn read_range(file: &mut File, offset: u64, len: usize) -> io::Result<Self> {
+        check_range(file, offset, len)?;
+        let mut buf = vec![0; len];
+        file.seek(SeekFrom::Start(offset))?;
+        file.read_exact(&mut buf)?;
+        Ok(Self {
+            bytes: Bytes::Copied(buf.into_boxed_slice()),
+            base: offset,
+        })
+    }
+
+    /// Returns `true` if the bytes are memory-mapped rather than copied.
+    ///
+    /// Empty ranges are never mapped.
+    pub fn is_mapped(&self) -> bool {
+        matches!(self.bytes, Bytes::Mapped(_))
+    }
+
+    /// Returns the number of bytes in the tranche.
+    pub fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    /// Returns `true` if the tranche has a length of 0.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the position in the file of the first byte of the tranche.
+    pub fn base_offset(&self) -> u64 {
+        self.base
+    }
+
+    /// Converts an offset relative to the start of the tranche, typically
+    /// one returned by `BasedBufTranche::offset`, into a position in the
+    /// file.
+    pub fn absolute_offset(&self, offset: usize) -> u64 {
+        self.base + offset as u64
+    }
+
+    /// Views the bytes as a slice.
+    pub fn as_slice(&self) -> &[u8] {
+        match &self.bytes {
+            Bytes::Mapped(mmap) => mmap,
+            Bytes::Copied(buf) => buf,
+        }
+    }
+
+    /// Borrows the bytes as a tranche.
+    pub fn as_tranche(&self) -> BufTranche<'_> {
+        BufTranche::new(self.as_slice())
+    }
+
+    /// Borrows the bytes as a based tranche, whose offsets are relative to
+    /// `base_offset`.
+    pub fn as_based_tranche(&self) -> BasedBufTranche<'_> {
+        BufTranche::new(self.as_slice()).into()
+    }
+}
+
+impl fmt::Debug for MappedBufTranche {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("MappedBufTranche")
+            .field("len", &self.len())
+            .field("base_offset", &self.base)
+            .field("mapped", &self.is_mapped())
+            .finish()
+    }
+}
+
+/// Returns the length of the given file, if it fits in the address space.
+fn whole_len(file: &File) -> io::Result<usize> {
+    usize::try_from(file.metadata()?.len()).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "file is larger than the address space",
+        )
+    })
+}
+
+/// Checks that the given range is within the given file.
+fn check_range(file: &File, offset: u64, len: usize) -> io::Result<()> {
+    let file_len = file.metadata()?.len();
+    match offset.checked_add(len as u64) {
+        Some(end) if end <= file_len => Ok(()),
+        _ => Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "range goes past the end of the file",
+        )),
+    }
+}
@@ -0,0 +1,181 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::core::{BasedBufTranche, BufTranche, UnexpectedEndError};
+
+/// An owned buffer of bytes, along with how many of them were consumed.
+///
+/// Owned tranches hand out based tranches borrowing from themselves, and
+/// remember how far those advanced once the borrow ends. This allows
+/// returning a buffer and a cursor into it from a function without any
+/// self-referential struct.
+///
+/// # Examples
+///
+/// ```
+/// # use tranche::{OwnedBufTranche, UnexpectedEndError};
+/// fn skip_header(buf: Vec<u8>) -> Result<OwnedBufTranche, UnexpectedEndError> {
+///     let mut owned = OwnedBufTranche::from(buf);
+///     owned.parse(|t| t.take_u16_be())?;
+///     Ok(owned)
+/// }
+///
+/// let mut owned = skip_header(vec![0, 2, 0xca, 0xfe, 0xff]).unwrap();
+/// assert_eq!(owned.offset(), 2);
+/// assert_eq!(owned.parse(|t| t.take_u16_be()).unwrap(), 0xcafe);
+/// assert_eq!(owned.as_slice(), &[0xff]);
+/// ```
+#[derive(Clone, Default)]
+pub struct OwnedBufTranche {
+    buf: Box<[u8]>,
+    offset: usize,
+}
+
+impl OwnedBufTranche {
+    /// Creates a new owned tranche with nothing consumed yet.
+    pub fn new(buf: Box<[u8]>) -> Self {
+        Self { buf, offset: 0 }
+    }
+
+    /// Returns the number of bytes left in the tranche.
+    pub fn len(&self) -> usize {
+        self.buf.len() - self.offset
+    }
+
+    /// Returns `true` if all bytes were consumed.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns how many bytes were consumed so far.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Views the bytes left in the tranche as a slice.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.buf[self.offset..]
+    }
+
+    /// Borrows the bytes left as a tranche.
+    ///
+    /// Taking things out of the returned tranche doesn't affect `self`, see
+    /// `advance` and `parse`.
+    pub fn as_tranche(&self) -> BufTranche<'_> {
+        BufTranche::new(self.as_slice())
+    }
+
+    /// Borrows the bytes left as a based tranche, whose offsets are relative
+    /// to the start of the whole buffer.
+    ///
+    /// Taking things out of the returned tranche doesn't affect `self`, see
+    /// `advance_to` and `parse`.
+    pub fn as_based_tranche(&self) -> BasedBufTranche<'_> {
+        let mut tranche = BasedBufTranche::new(&self.buf);
+        tranche.take_front(self.offset).unwrap();
+        tranche
+    }
+
+    /// Marks the next `n` bytes as consumed.
+    ///
+    /// Returns `Err(_)` if fewer than `n` bytes are left.
+    pub fn advance(&mut self, n: usize) -> Result<(), UnexpectedEndError> {
+        let len = self.len();
+        if n > len {
+            return Err(UnexpectedEndError::new(n, len));
+        }
+        self.offset += n;
+        Ok(())
+    }
+
+    /// Marks everything up to the given offset as consumed, typically one
+    /// returned by `BasedBufTranche::offset` on a tranche obtained from
+    /// `as_based_tranche`.
+    ///
+    /// Returns `Err(_)` if the offset is past the end of the buffer. Offsets
+    /// before the current one are allowed, and rewind the tranche.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tranche::OwnedBufTranche;
+    /// let mut owned = OwnedBufTranche::from(vec![1, 2, 3]);
+    /// let mut tranche = owned.as_based_tranche();
+    /// tranche.take_u16_le().unwrap();
+    /// let offset = tranche.offset();
+    /// owned.advance_to(offset).unwrap();
+    /// assert_eq!(owned.as_slice(), &[3]);
+    /// ```
+    pub fn advance_to(&mut self, offset: usize) -> Result<(), UnexpectedEndError> {
+        let len = self.buf.len();
+        if offset > len {
+            return Err(UnexpectedEndError::new(offset, len));
+        }
+        self.offset = offset;
+        Ok(())
+    }
+
+    /// Runs a parser on the bytes left, and marks what it took as consumed
+    /// if it succeeds.
+    ///
+    /// Nothing is consumed if the parser fails. Panics if the parser replaced
+    /// the tranche it is given with one which doesn't borrow from this
+    /// buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tranche::OwnedBufTranche;
+    /// let mut owned = OwnedBufTranche::from(vec![1, 2, 3]);
+    /// assert!(owned.parse(|t| t.take_u32_le()).is_err());
+    /// assert_eq!(owned.offset(), 0);
+    ///
+    /// let pair = owned.parse(|t| Ok::<_, ()>((t.take_u8().unwrap(), t.offset())));
+    /// assert_eq!(pair, Ok((1, 1)));
+    /// assert_eq!(owned.offset(), 1);
+    /// ```
+    pub fn parse<T, E>(
+        &mut self,
+        f: impl FnOnce(&mut BasedBufTranche<'_>) -> Result<T, E>,
+    ) -> Result<T, E> {
+        let mut tranche = self.as_based_tranche();
+        let value = f(&mut tranche)?;
+        let offset = (tranche.as_ptr() as usize).wrapping_sub(self.buf.as_ptr() as usize);
+        assert!(
+            offset <= self.buf.len() && tranche.len() <= self.buf.len() - offset,
+            "tranche was replaced by an unrelated one",
+        );
+        self.offset = offset;
+        Ok(value)
+    }
+
+    /// Consumes the owned tranche, returning the whole buffer.
+    pub fn into_inner(self) -> Box<[u8]> {
+        self.buf
+    }
+}
+
+impl From<Box<[u8]>> for OwnedBufTranche {
+    fn from(buf: Box<[u8]>) -> Self {
+        Self::new(buf)
+    }
+}
+
+impl From<Vec<u8>> for OwnedBufTranche {
+    fn from(buf: Vec<u8>) -> Self {
+        Self::new(buf.into_boxed_slice())
+    }
+}
+
+impl fmt::Debug for OwnedBufTranche {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        self.as_slice().fmt(fmt)
+    }
+}
@@ -0,0 +1,172 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use crate::core::BufTranche;
+use crate::core::UnexpectedEndError;
+
+/// An error from a parser which may succeed if given more input.
+///
+/// Parsers returning this error can use `?` on tranche operations, as
+/// `UnexpectedEndError` converts into `ResumableError::Incomplete`. Only the
+/// end of the whole input should be reported as incomplete, running out of
+/// bytes in a sub-tranche with a declared length is a malformed input.
+#[derive(Clone, Debug)]
+pub enum ResumableError<E> {
+    /// The input ended too early, at least `needed` more bytes are required.
+    Incomplete {
+        /// How many more bytes are required at least.
+        needed: usize,
+    },
+    /// The input is malformed, no amount of additional bytes will help.
+    Malformed(E),
+}
+
+impl<E> ResumableError<E> {
+    /// Returns `true` if more input is needed.
+    pub fn is_incomplete(&self) -> bool {
+        matches!(self, ResumableError::Incomplete { .. })
+    }
+}
+
+impl<E> From<UnexpectedEndError> for ResumableError<E> {
+    fn from(error: UnexpectedEndError) -> Self {
+        let needed = error.needed() - error.len();
+        ResumableError::Incomplete { needed }
+    }
+}
+
+impl<E> fmt::Display for ResumableError<E>
+where
+    E: fmt::Display,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ResumableError::Incomplete { needed } => {
+                write!(fmt, "incomplete input (needed {} more)", needed)
+            }
+            ResumableError::Malformed(error) => error.fmt(fmt),
+        }
+    }
+}
+
+/// A buffer of bytes received incrementally, from which complete frames are
+/// parsed.
+///
+/// Bytes are appended with `extend_from_slice` as they arrive, and
+/// `next_frame` runs a parser from the offset where the last complete frame
+/// ended. A parser reporting `ResumableError::Incomplete` is simply run again
+/// once more bytes are available.
+///
+/// # Examples
+///
+/// ```
+/// # use tranche::{BufTranche, FrameBuffer, ResumableError};
+/// fn frame<'a>(t: &mut BufTranche<'a>) -> Result<&'a [u8], ResumableError<&'static str>> {
+///     let len = t.take_u8()?;
+///     if len == 0 {
+///         return Err(ResumableError::Malformed("empty frame"));
+///     }
+///     Ok(t.take_front(len as usize)?.as_slice())
+/// }
+///
+/// let mut buffer = FrameBuffer::new();
+/// let mut frames = vec![];
+/// for chunk in [&[3, b'a'][..], &[b'b', b'c', 1], &[b'd']] {
+///     buffer.extend_from_slice(chunk);
+///     while let Some((payload, len)) = buffer.next_frame(frame).unwrap() {
+///         frames.push((payload.to_vec(), len));
+///     }
+/// }
+/// assert_eq!(frames, [(b"abc".to_vec(), 4), (b"d".to_vec(), 2)]);
+///
+/// buffer.extend_from_slice(&[0]);
+/// assert_eq!(buffer.next_frame(frame).unwrap_err(), "empty frame");
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default)]
+pub struct FrameBuffer {
+    buf: Vec<u8>,
+    start: usize,
+    offset: u64,
+    needed: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl FrameBuffer {
+    /// Creates a new empty frame buffer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of bytes received but not consumed by a complete
+    /// frame yet.
+    pub fn len(&self) -> usize {
+        self.buf.len() - self.start
+    }
+
+    /// Returns `true` if all bytes received were consumed.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns how many bytes were consumed by complete frames so far.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns how many more bytes the last incomplete parse needed at least,
+    /// or 0 if the last parse was not incomplete.
+    pub fn needed(&self) -> usize {
+        self.needed
+    }
+
+    /// Views the bytes not consumed yet as a slice.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.buf[self.start..]
+    }
+
+    /// Appends newly received bytes to the buffer.
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+        if self.start > 0 && self.start >= self.len() {
+            self.buf.drain(..self.start);
+            self.start = 0;
+        }
+        self.buf.extend_from_slice(bytes);
+        self.needed = self.needed.saturating_sub(bytes.len());
+    }
+
+    /// Runs a parser on the bytes not consumed yet.
+    ///
+    /// Returns the parsed frame along with how many bytes it consumed,
+    /// `Ok(None)` if the parser needs more input, or `Err(_)` if the input is
+    /// malformed. Nothing is consumed unless a complete frame is returned.
+    pub fn next_frame<'a, T, E>(
+        &'a mut self,
+        parser: impl FnOnce(&mut BufTranche<'a>) -> Result<T, ResumableError<E>>,
+    ) -> Result<Option<(T, usize)>, E> {
+        let mut tranche = BufTranche::new(&self.buf[self.start..]);
+        let before = tranche.len();
+        match parser(&mut tranche) {
+            Ok(frame) => {
+                let len = before - tranche.len();
+                self.start += len;
+                self.offset += len as u64;
+                self.needed = 0;
+                Ok(Some((frame, len)))
+            }
+            Err(ResumableError::Incomplete { needed }) => {
+                self.needed = needed;
+                Ok(None)
+            }
+            Err(ResumableError::Malformed(error)) => Err(error),
+        }
+    }
+}
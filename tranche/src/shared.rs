@@ -0,0 +1,198 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::core::{BasedBufTranche, BufTranche, UnexpectedEndError};
+
+macro_rules! shared_tranche_taker {
+    ($ty:ident $endian:tt $take:ident $from:ident $put:ident $to:ident $expect:ident) => {
+        taker_with_computed_doc! {
+            /// Returns a
+            #[doc = concat!("`", stringify!($ty), "`")]
+            /// by taking the first
+            #[doc = concat!("`mem::size_of::<", stringify!($ty), ">()`")]
+            /// bytes out of the tranche in
+            #[doc = $endian]
+            /// endian order.
+            ///
+            /// Returns `Err(_)` if `self` is not long enough.
+            #[inline]
+            pub fn $take(&mut self) -> Result<$ty, UnexpectedEndError> {
+                let mut tranche = self.as_tranche();
+                let value = tranche.$take()?;
+                self.start = self.end - tranche.len();
+                Ok(value)
+            }
+        }
+    };
+}
+
+/// A shared tranche of bytes.
+///
+/// Shared tranches own their bytes through an `Arc<[u8]>`, so they can
+/// outlive the buffer they were parsed from and be sent to other threads.
+/// Cloning a shared tranche and taking sub-tranches out of it never copies
+/// the bytes themselves.
+///
+/// # Examples
+///
+/// ```
+/// # use tranche::SharedBufTranche;
+/// let mut tranche = SharedBufTranche::from(vec![0, 3, b'a', b'b', b'c', 0xff]);
+/// let len = tranche.take_u16_be().unwrap();
+/// let name = tranche.take_front(len as usize).unwrap();
+///
+/// let handle = std::thread::spawn(move || name.as_slice().to_vec());
+/// assert_eq!(handle.join().unwrap(), b"abc");
+/// assert_eq!(tranche.as_slice(), &[0xff]);
+/// ```
+#[derive(Clone)]
+pub struct SharedBufTranche {
+    buf: Arc<[u8]>,
+    start: usize,
+    end: usize,
+}
+
+impl SharedBufTranche {
+    /// Creates a new shared tranche covering the whole given buffer.
+    pub fn new(buf: Arc<[u8]>) -> Self {
+        let end = buf.len();
+        Self { buf, start: 0, end }
+    }
+
+    /// Returns the number of bytes in the tranche.
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Returns `true` if the tranche has a length of 0.
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Returns the starting offset of this tranche in the shared buffer.
+    pub fn offset(&self) -> usize {
+        self.start
+    }
+
+    /// Returns the shared buffer this tranche is a part of.
+    pub fn buffer(&self) -> &Arc<[u8]> {
+        &self.buf
+    }
+
+    /// Views the tranche's bytes as a slice.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.buf[self.start..self.end]
+    }
+
+    /// Borrows the tranche as a tranche of bytes.
+    ///
+    /// Taking things out of the returned tranche doesn't affect `self`.
+    pub fn as_tranche(&self) -> BufTranche<'_> {
+        BufTranche::new(self.as_slice())
+    }
+
+    /// Borrows the tranche as a based tranche of bytes, whose offsets are
+    /// relative to the start of the shared buffer.
+    ///
+    /// Taking things out of the returned tranche doesn't affect `self`.
+    pub fn as_based_tranche(&self) -> BasedBufTranche<'_> {
+        let mut tranche = BasedBufTranche::from(BufTranche::new(&self.buf[..self.end]));
+        tranche.take_front(self.start).unwrap();
+        tranche
+    }
+
+    /// Takes the first `n` bytes out of the tranche.
+    ///
+    /// Returns a new shared tranche with the first `n` bytes of `self`,
+    /// sharing the same buffer, or `Err(_)` if it is not long enough.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tranche::SharedBufTranche;
+    /// let mut v = SharedBufTranche::from(&b"baguette"[..]);
+    /// let front = v.take_front(3).unwrap();
+    /// assert_eq!(front.as_slice(), b"bag");
+    /// assert_eq!(v.as_slice(), b"uette");
+    /// assert_eq!(v.offset(), 3);
+    ///
+    /// let err = v.take_front(6).unwrap_err();
+    /// assert_eq!(err.needed(), 6);
+    /// assert_eq!(err.len(), 5);
+    /// ```
+    pub fn take_front(&mut self, n: usize) -> Result<Self, UnexpectedEndError> {
+        let len = self.len();
+        if n > len {
+            return Err(UnexpectedEndError::new(n, len));
+        }
+        let start = self.start;
+        self.start += n;
+        Ok(Self {
+            buf: self.buf.clone(),
+            start,
+            end: self.start,
+        })
+    }
+
+    /// Takes the first `u8` out of the tranche.
+    ///
+    /// Returns `Err(_)` if `self` is not long enough.
+    pub fn take_u8(&mut self) -> Result<u8, UnexpectedEndError> {
+        let byte = *self.as_tranche().take_first()?;
+        self.start += 1;
+        Ok(byte)
+    }
+
+    /// Takes the first `i8` out of the tranche.
+    ///
+    /// Returns `Err(_)` if `self` is not long enough.
+    pub fn take_i8(&mut self) -> Result<i8, UnexpectedEndError> {
+        Ok(self.take_u8()? as i8)
+    }
+
+    call_for_each_taker!(shared_tranche_taker);
+}
+
+impl From<Arc<[u8]>> for SharedBufTranche {
+    fn from(buf: Arc<[u8]>) -> Self {
+        Self::new(buf)
+    }
+}
+
+impl From<Box<[u8]>> for SharedBufTranche {
+    fn from(buf: Box<[u8]>) -> Self {
+        Self::new(buf.into())
+    }
+}
+
+impl From<Vec<u8>> for SharedBufTranche {
+    fn from(buf: Vec<u8>) -> Self {
+        Self::new(buf.into())
+    }
+}
+
+impl From<&[u8]> for SharedBufTranche {
+    fn from(buf: &[u8]) -> Self {
+        Self::new(buf.into())
+    }
+}
+
+impl Default for SharedBufTranche {
+    fn default() -> Self {
+        Self::from(&[][..])
+    }
+}
+
+impl fmt::Debug for SharedBufTranche {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        self.as_slice().fmt(fmt)
+    }
+}
@@ -5,20 +5,21 @@
 // except according to those terms.
 
 use core::cmp;
+use core::convert::TryFrom;
+use core::fmt;
 use core::str;
 
-use std::error::Error;
 use std::io;
 
-use crate::{BasedBufTranche, BufTranche, UnexpectedEndError};
+use crate::{
+    BasedBufTranche, BufTranche, ContextError, ExpectError, SliceWriter, StreamTranche,
+    TakeAsError, TakeEnumError, TakeLenError, TrailingDataError, UnexpectedEndError, VecWriter,
+};
 
 impl io::Read for BufTranche<'_> {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        let slice = self
-            .take_front(cmp::max(self.len(), buf.len()))
-            .unwrap()
-            .as_slice();
-        let len = slice.len();
+        let len = cmp::min(self.len(), buf.len());
+        let slice = self.take_front(len).unwrap().as_slice();
         if len == 1 {
             buf[0] = slice[0];
         } else {
@@ -26,12 +27,66 @@ impl io::Read for BufTranche<'_> {
         }
         Ok(len)
     }
+
+    fn read_vectored(&mut self, bufs: &mut [io::IoSliceMut<'_>]) -> io::Result<usize> {
+        let mut read = 0;
+        for buf in bufs {
+            if self.as_slice().is_empty() {
+                break;
+            }
+            read += self.read(buf)?;
+        }
+        Ok(read)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        let len = self.len();
+        match self.take_front(buf.len()) {
+            Ok(front) => {
+                buf.copy_from_slice(front.as_slice());
+                Ok(())
+            }
+            Err(err) => {
+                self.take_front(len).unwrap();
+                Err(err.into())
+            }
+        }
+    }
+
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        let len = self.len();
+        buf.extend_from_slice(self.take_front(len).unwrap().as_slice());
+        Ok(len)
+    }
+
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        let s = str::from_utf8(self.as_slice()).map_err(invalid_utf8)?;
+        buf.push_str(s);
+        self.take_front(s.len()).unwrap();
+        Ok(s.len())
+    }
 }
 
 impl io::Read for BasedBufTranche<'_> {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         self.inner.read(buf)
     }
+
+    fn read_vectored(&mut self, bufs: &mut [io::IoSliceMut<'_>]) -> io::Result<usize> {
+        self.inner.read_vectored(bufs)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        self.inner.read_exact(buf)
+    }
+
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        self.inner.read_to_end(buf)
+    }
+
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        self.inner.read_to_string(buf)
+    }
 }
 
 impl io::BufRead for BufTranche<'_> {
@@ -40,9 +95,21 @@ impl io::BufRead for BufTranche<'_> {
     }
 
     fn consume(&mut self, len: usize) {
-        if let Err(err) = self.take_front(len) {
-            panic!("{}", err);
-        }
+        let len = cmp::min(len, self.len());
+        self.take_front(len).unwrap();
+    }
+
+    fn read_until(&mut self, byte: u8, buf: &mut Vec<u8>) -> io::Result<usize> {
+        let len = line_len(self.as_slice(), byte);
+        buf.extend_from_slice(self.take_front(len).unwrap().as_slice());
+        Ok(len)
+    }
+
+    fn read_line(&mut self, buf: &mut String) -> io::Result<usize> {
+        let len = line_len(self.as_slice(), b'\n');
+        let line = self.take_front(len).unwrap().as_slice();
+        buf.push_str(str::from_utf8(line).map_err(invalid_utf8)?);
+        Ok(len)
     }
 }
 
@@ -54,6 +121,117 @@ impl io::BufRead for BasedBufTranche<'_> {
     fn consume(&mut self, len: usize) {
         self.inner.consume(len)
     }
+
+    fn read_until(&mut self, byte: u8, buf: &mut Vec<u8>) -> io::Result<usize> {
+        self.inner.read_until(byte, buf)
+    }
+
+    fn read_line(&mut self, buf: &mut String) -> io::Result<usize> {
+        self.inner.read_line(buf)
+    }
+}
+
+impl io::Seek for BasedBufTranche<'_> {
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        let offset = match pos {
+            io::SeekFrom::Start(offset) => Some(offset),
+            io::SeekFrom::Current(delta) => (self.offset() as u64).checked_add_signed(delta),
+            io::SeekFrom::End(delta) => (self.extent() as u64).checked_add_signed(delta),
+        };
+        let offset = offset
+            .and_then(|offset| usize::try_from(offset).ok())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "invalid seek to a negative or overflowing position",
+                )
+            })?;
+        self.seek_to(offset).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek past the end of the tranche",
+            )
+        })?;
+        Ok(offset as u64)
+    }
+
+    fn stream_position(&mut self) -> io::Result<u64> {
+        Ok(self.offset() as u64)
+    }
+}
+
+/// Returns the length of the first line of `slice` terminated by `byte`,
+/// including the terminator, or the length of the whole slice if it contains
+/// no terminator.
+fn line_len(slice: &[u8], byte: u8) -> usize {
+    slice
+        .iter()
+        .position(|&b| b == byte)
+        .map_or(slice.len(), |pos| pos + 1)
+}
+
+fn invalid_utf8(_: str::Utf8Error) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        "stream did not contain valid UTF-8",
+    )
+}
+
+impl<R> io::Read for StreamTranche<R>
+where
+    R: io::Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = io::BufRead::fill_buf(self)?;
+        let len = cmp::min(available.len(), buf.len());
+        buf[..len].copy_from_slice(&available[..len]);
+        self.consume(len);
+        Ok(len)
+    }
+}
+
+impl<R> io::BufRead for StreamTranche<R>
+where
+    R: io::Read,
+{
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.buffered().is_empty() {
+            self.read_more()?;
+        }
+        Ok(self.buffered().as_slice())
+    }
+
+    fn consume(&mut self, len: usize) {
+        StreamTranche::consume(self, len)
+    }
+}
+
+impl io::Write for SliceWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = cmp::min(self.remaining(), buf.len());
+        self.put_slice(&buf[..len])?;
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl io::Write for VecWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.put_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.put_slice(buf);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
 }
 
 impl From<UnexpectedEndError> for io::Error {
@@ -62,8 +240,60 @@ impl From<UnexpectedEndError> for io::Error {
     }
 }
 
-impl Error for UnexpectedEndError {
-    fn description(&self) -> &str {
-        "unexpected end"
+impl From<TakeAsError> for io::Error {
+    fn from(error: TakeAsError) -> Self {
+        match error {
+            TakeAsError::UnexpectedEnd(error) => error.into(),
+            TakeAsError::Misaligned(error) => io::Error::new(io::ErrorKind::InvalidData, error),
+        }
+    }
+}
+
+impl<T> From<ExpectError<T>> for io::Error
+where
+    T: fmt::Debug,
+{
+    fn from(error: ExpectError<T>) -> Self {
+        crate::Error::from(error).into()
+    }
+}
+
+impl<R> From<TakeEnumError<R>> for io::Error
+where
+    R: Into<u64>,
+{
+    fn from(error: TakeEnumError<R>) -> Self {
+        crate::Error::from(error).into()
+    }
+}
+
+impl From<TakeLenError> for io::Error {
+    fn from(error: TakeLenError) -> Self {
+        crate::Error::from(error).into()
+    }
+}
+
+impl From<TrailingDataError> for io::Error {
+    fn from(error: TrailingDataError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, error)
+    }
+}
+
+impl From<crate::Error> for io::Error {
+    fn from(error: crate::Error) -> Self {
+        match error {
+            crate::Error::UnexpectedEnd(error) => error.into(),
+            error => io::Error::new(io::ErrorKind::InvalidData, error),
+        }
+    }
+}
+
+impl From<ContextError> for io::Error {
+    fn from(error: ContextError) -> Self {
+        let kind = match error.error() {
+            crate::Error::UnexpectedEnd(_) => io::ErrorKind::UnexpectedEof,
+            _ => io::ErrorKind::InvalidData,
+        };
+        io::Error::new(kind, error)
     }
 }
@@ -0,0 +1,264 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use core::cmp;
+use core::fmt;
+use core::mem;
+
+use std::io;
+
+use crate::core::{BufTranche, UnexpectedEndError};
+
+const DEFAULT_MAX_WINDOW: usize = 64 * 1024;
+const INITIAL_CAPACITY: usize = 8 * 1024;
+
+macro_rules! stream_tranche_taker {
+    ($ty:ident $endian:tt $take:ident $from:ident $put:ident $to:ident $expect:ident) => {
+        taker_with_computed_doc! {
+            /// Returns a
+            #[doc = concat!("`", stringify!($ty), "`")]
+            /// by taking the first
+            #[doc = concat!("`mem::size_of::<", stringify!($ty), ">()`")]
+            /// bytes out of the stream in
+            #[doc = $endian]
+            /// endian order, reading more from the underlying reader if
+            /// needed.
+            ///
+            /// The internal offset is incremented accordingly.
+            ///
+            /// Returns `Err(_)` if the stream ends too early or if the reader
+            /// fails.
+            #[inline]
+            pub fn $take(&mut self) -> io::Result<$ty> {
+                Ok(self.take_front(mem::size_of::<$ty>())?.$take()?)
+            }
+        }
+    };
+}
+
+/// A tranche of bytes read from a `std::io::Read` value.
+///
+/// Stream tranches own a buffer which is refilled from the underlying reader
+/// whenever a taker needs more bytes than what is buffered, so that inputs
+/// larger than memory can be parsed. Sub-tranches borrowed from the buffer
+/// can be up to the maximum window in size, and the absolute offset in the
+/// stream is tracked like with `BasedTranche::offset`.
+///
+/// This type implements `std::io::Read` and `std::io::BufRead`.
+///
+/// # Examples
+///
+/// ```
+/// # use tranche::StreamTranche;
+/// let input: &[u8] = &[0, 0, 0, 3, b'a', b'b', b'c', 0xff];
+/// let mut stream = StreamTranche::new(input);
+///
+/// let len = stream.take_u32_be().unwrap();
+/// let name = stream.take_front(len as usize).unwrap();
+/// assert_eq!(name.as_slice(), b"abc");
+/// assert_eq!(stream.offset(), 7);
+/// assert_eq!(stream.take_u8().unwrap(), 0xff);
+/// assert!(stream.take_u8().is_err());
+/// ```
+pub struct StreamTranche<R> {
+    reader: R,
+    buf: Vec<u8>,
+    start: usize,
+    end: usize,
+    offset: u64,
+    max_window: usize,
+}
+
+impl<R> StreamTranche<R>
+where
+    R: io::Read,
+{
+    /// Creates a new stream tranche with a maximum window of 64 KiB.
+    pub fn new(reader: R) -> Self {
+        Self::with_max_window(reader, DEFAULT_MAX_WINDOW)
+    }
+
+    /// Creates a new stream tranche with the given maximum window, which is
+    /// the largest number of bytes that can be borrowed at once with
+    /// `take_front`.
+    ///
+    /// Panics if `max_window` is 0.
+    pub fn with_max_window(reader: R, max_window: usize) -> Self {
+        assert!(max_window > 0, "maximum window must not be 0");
+        Self {
+            reader,
+            buf: vec![0; cmp::min(INITIAL_CAPACITY, max_window)],
+            start: 0,
+            end: 0,
+            offset: 0,
+            max_window,
+        }
+    }
+
+    /// Returns how many bytes were taken out of the stream so far.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns the maximum window of this stream tranche.
+    pub fn max_window(&self) -> usize {
+        self.max_window
+    }
+
+    /// Returns the bytes currently buffered, without reading anything.
+    pub fn buffered(&self) -> BufTranche<'_> {
+        BufTranche::new(&self.buf[self.start..self.end])
+    }
+
+    /// Makes sure at least `n` bytes are buffered, reading more from the
+    /// underlying reader if needed, and returns the buffered bytes.
+    ///
+    /// Returns `Err(_)` with `std::io::ErrorKind::InvalidInput` if `n` is
+    /// larger than the maximum window, or with
+    /// `std::io::ErrorKind::UnexpectedEof` if the stream ends before `n`
+    /// bytes could be buffered.
+    pub fn fill(&mut self, n: usize) -> io::Result<BufTranche<'_>> {
+        if n > self.max_window {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "requested more bytes than the maximum window",
+            ));
+        }
+        if self.end - self.start < n {
+            if self.buf.len() - self.start < n {
+                self.compact();
+            }
+            if self.buf.len() < n {
+                let capacity = cmp::max(n, cmp::min(self.buf.len() * 2, self.max_window));
+                self.buf.resize(capacity, 0);
+            }
+            while self.end - self.start < n {
+                if self.read_more()? == 0 {
+                    return Err(UnexpectedEndError::new(n, self.end - self.start).into());
+                }
+            }
+        }
+        Ok(self.buffered())
+    }
+
+    /// Takes the first `n` bytes out of the stream, reading more from the
+    /// underlying reader if needed.
+    ///
+    /// Returns a tranche borrowing the `n` bytes from the internal buffer,
+    /// or `Err(_)` as described in `fill`. The internal offset is
+    /// incremented accordingly.
+    pub fn take_front(&mut self, n: usize) -> io::Result<BufTranche<'_>> {
+        self.fill(n)?;
+        let start = self.start;
+        self.consume(n);
+        Ok(BufTranche::new(&self.buf[start..start + n]))
+    }
+
+    /// Skips the first `n` bytes of the stream, which may be larger than the
+    /// maximum window.
+    ///
+    /// Returns `Err(_)` with `std::io::ErrorKind::UnexpectedEof` if the
+    /// stream ends too early, in which case the offset reflects how many
+    /// bytes were skipped anyway.
+    pub fn skip(&mut self, mut n: u64) -> io::Result<()> {
+        while n > 0 {
+            if self.start == self.end {
+                self.fill(1)?;
+            }
+            let buffered = (self.end - self.start) as u64;
+            let skipped = cmp::min(n, buffered);
+            self.consume(skipped as usize);
+            n -= skipped;
+        }
+        Ok(())
+    }
+
+    /// Takes the first `u8` out of the stream, reading more from the
+    /// underlying reader if needed.
+    ///
+    /// The internal offset is incremented accordingly.
+    ///
+    /// Returns `Err(_)` if the stream ends too early or if the reader fails.
+    pub fn take_u8(&mut self) -> io::Result<u8> {
+        Ok(self.take_front(1)?.take_u8()?)
+    }
+
+    /// Takes the first `i8` out of the stream, reading more from the
+    /// underlying reader if needed.
+    ///
+    /// The internal offset is incremented accordingly.
+    ///
+    /// Returns `Err(_)` if the stream ends too early or if the reader fails.
+    pub fn take_i8(&mut self) -> io::Result<i8> {
+        Ok(self.take_front(1)?.take_i8()?)
+    }
+
+    call_for_each_taker!(stream_tranche_taker);
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Consumes the stream tranche, returning the underlying reader.
+    ///
+    /// Bytes which were buffered but not taken are lost.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Marks the first `n` buffered bytes as taken.
+    pub(crate) fn consume(&mut self, n: usize) {
+        let n = cmp::min(n, self.end - self.start);
+        self.start += n;
+        self.offset += n as u64;
+    }
+
+    /// Reads more bytes from the underlying reader into the buffer,
+    /// returning how many were read, which is 0 only at the end of the
+    /// stream.
+    pub(crate) fn read_more(&mut self) -> io::Result<usize> {
+        if self.end == self.buf.len() {
+            if self.start > 0 {
+                self.compact();
+            } else {
+                let capacity = cmp::min(self.buf.len() * 2, self.max_window);
+                self.buf.resize(capacity, 0);
+            }
+        }
+        loop {
+            match self.reader.read(&mut self.buf[self.end..]) {
+                Ok(read) => {
+                    self.end += read;
+                    return Ok(read);
+                }
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Moves the buffered bytes to the start of the buffer.
+    fn compact(&mut self) {
+        self.buf.copy_within(self.start..self.end, 0);
+        self.end -= self.start;
+        self.start = 0;
+    }
+}
+
+impl<R> fmt::Debug for StreamTranche<R>
+where
+    R: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("StreamTranche")
+            .field("reader", &self.reader)
+            .field("buffered", &&self.buf[self.start..self.end])
+            .field("offset", &self.offset)
+            .field("max_window", &self.max_window)
+            .finish()
+    }
+}
@@ -0,0 +1,126 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use core::cmp;
+use core::fmt;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use std::io::{self, BufRead};
+
+use bytes::{Buf, BytesMut};
+use tokio::io::{AsyncBufRead, AsyncRead, ReadBuf};
+use tokio_util::codec::Decoder;
+
+use crate::{BasedBufTranche, BufTranche, ResumableError};
+
+macro_rules! impl_async_read {
+    ($ty:ty) => {
+        impl AsyncRead for $ty {
+            fn poll_read(
+                self: Pin<&mut Self>,
+                _: &mut Context<'_>,
+                buf: &mut ReadBuf<'_>,
+            ) -> Poll<io::Result<()>> {
+                let this = self.get_mut();
+                let len = cmp::min(this.len(), buf.remaining());
+                buf.put_slice(this.take_front(len).unwrap().as_slice());
+                Poll::Ready(Ok(()))
+            }
+        }
+
+        impl AsyncBufRead for $ty {
+            fn poll_fill_buf(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+                Poll::Ready(Ok(self.get_mut().as_slice()))
+            }
+
+            fn consume(self: Pin<&mut Self>, amt: usize) {
+                BufRead::consume(self.get_mut(), amt)
+            }
+        }
+    };
+}
+
+impl_async_read!(BufTranche<'_>);
+impl_async_read!(BasedBufTranche<'_>);
+
+/// A `tokio_util::codec::Decoder` running a tranche parser on the bytes
+/// received so far.
+///
+/// The parser reports that it needs more bytes with
+/// `ResumableError::Incomplete`, which is what `UnexpectedEndError` converts
+/// into, in which case nothing is consumed and the parser is run again from
+/// the same position once more bytes are received. When it succeeds, exactly
+/// the bytes it took are consumed.
+///
+/// # Examples
+///
+/// ```
+/// # use tranche::{BufTranche, ResumableError, TrancheDecoder};
+/// # use bytes::BytesMut;
+/// # use tokio_util::codec::Decoder;
+/// let mut decoder = TrancheDecoder::new(|t: &mut BufTranche| {
+///     let len = t.take_u8()?;
+///     let body = t.take_front(len as usize)?;
+///     Ok::<_, ResumableError<std::io::Error>>(body.as_slice().to_vec())
+/// });
+///
+/// let mut src = BytesMut::from(&[3, b'a', b'b'][..]);
+/// assert!(decoder.decode(&mut src).unwrap().is_none());
+/// src.extend_from_slice(b"c!");
+/// assert_eq!(decoder.decode(&mut src).unwrap().unwrap(), b"abc");
+/// assert_eq!(&src[..], b"!");
+/// ```
+pub struct TrancheDecoder<F> {
+    parser: F,
+}
+
+impl<F> TrancheDecoder<F> {
+    /// Creates a new decoder running the given parser.
+    pub fn new(parser: F) -> Self {
+        Self { parser }
+    }
+
+    /// Consumes the decoder, returning the parser.
+    pub fn into_inner(self) -> F {
+        self.parser
+    }
+}
+
+impl<F, T, E> Decoder for TrancheDecoder<F>
+where
+    F: FnMut(&mut BufTranche<'_>) -> Result<T, ResumableError<E>>,
+    E: From<io::Error>,
+{
+    type Item = T;
+    type Error = E;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<T>, E> {
+        let mut tranche = BufTranche::new(&src[..]);
+        match (self.parser)(&mut tranche) {
+            Ok(item) => {
+                let offset = (tranche.as_ptr() as usize).wrapping_sub(src.as_ptr() as usize);
+                assert!(
+                    offset <= src.len() && tranche.len() <= src.len() - offset,
+                    "tranche was replaced by an unrelated one",
+                );
+                src.advance(offset);
+                Ok(Some(item))
+            }
+            Err(ResumableError::Incomplete { needed }) => {
+                src.reserve(needed);
+                Ok(None)
+            }
+            Err(ResumableError::Malformed(error)) => Err(error),
+        }
+    }
+}
+
+impl<F> fmt::Debug for TrancheDecoder<F> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("TrancheDecoder").finish()
+    }
+}
@@ -0,0 +1,197 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use alloc::vec::{self, Vec};
+use core::fmt;
+
+use crate::core::{Tranche, UnexpectedEndError};
+
+/// An owning tranche of `T`.
+///
+/// Vec tranches are to tranches what `vec::IntoIter<T>` is to
+/// `slice::Iter<'_, T>`: elements are moved out of them instead of being
+/// borrowed. Elements that are never taken out are dropped along with the
+/// tranche.
+pub struct VecTranche<T> {
+    pub(crate) inner: vec::IntoIter<T>,
+}
+
+impl<T> VecTranche<T> {
+    /// Creates a new owning tranche of `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tranche::VecTranche;
+    /// let parisien = VecTranche::new(vec![
+    ///     String::from("baguette"),
+    ///     String::from("jambon"),
+    ///     String::from("beurre"),
+    /// ]);
+    /// ```
+    pub fn new(vec: Vec<T>) -> Self {
+        Self {
+            inner: vec.into_iter(),
+        }
+    }
+
+    /// Returns the number of elements in the tranche.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if the tranche has a length of 0.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Takes the first element out of the tranche.
+    ///
+    /// Returns the first element of `self`, or `Err(_)` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tranche::VecTranche;
+    /// let mut v = VecTranche::new(vec![10, 40, 30]);
+    /// assert_eq!(v.take_first().unwrap(), 10);
+    /// assert_eq!(v.as_slice(), &[40, 30]);
+    ///
+    /// let mut w = VecTranche::<i32>::new(vec![]);
+    /// let err = w.take_first().unwrap_err();
+    /// assert_eq!(err.needed(), 1);
+    /// assert_eq!(err.len(), 0);
+    /// ```
+    pub fn take_first(&mut self) -> Result<T, UnexpectedEndError> {
+        self.inner
+            .next()
+            .ok_or_else(|| UnexpectedEndError::new(1, 0))
+    }
+
+    /// Takes the last element out of the tranche.
+    ///
+    /// Returns the last element of `self`, or `Err(_)` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tranche::VecTranche;
+    /// let mut v = VecTranche::new(vec![10, 40, 30]);
+    /// assert_eq!(v.take_last().unwrap(), 30);
+    /// assert_eq!(v.as_slice(), &[10, 40]);
+    /// ```
+    pub fn take_last(&mut self) -> Result<T, UnexpectedEndError> {
+        self.inner
+            .next_back()
+            .ok_or_else(|| UnexpectedEndError::new(1, 0))
+    }
+
+    /// Takes the first `n` elements out of the tranche.
+    ///
+    /// Returns a new tranche with the first `n` elements of `self`, or
+    /// `Err(_)` if it is not long enough, in which case nothing is taken.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tranche::VecTranche;
+    /// let mut v = VecTranche::new(vec![10, 40, 30]);
+    /// assert_eq!(v.take_front(2).unwrap().as_slice(), &[10, 40]);
+    /// assert_eq!(v.as_slice(), &[30]);
+    ///
+    /// let err = v.take_front(3).unwrap_err();
+    /// assert_eq!(err.needed(), 3);
+    /// assert_eq!(err.len(), 1);
+    /// ```
+    pub fn take_front(&mut self, n: usize) -> Result<Self, UnexpectedEndError> {
+        let len = self.len();
+        if n > len {
+            return Err(UnexpectedEndError::new(n, len));
+        }
+        Ok(Self::new(self.inner.by_ref().take(n).collect()))
+    }
+
+    /// Takes the last `n` elements out of the tranche.
+    ///
+    /// Returns a new tranche with the last `n` elements of `self` in their
+    /// original order, or `Err(_)` if it is not long enough, in which case
+    /// nothing is taken.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tranche::VecTranche;
+    /// let mut v = VecTranche::new(vec![10, 40, 30]);
+    /// assert_eq!(v.take_back(2).unwrap().as_slice(), &[40, 30]);
+    /// assert_eq!(v.as_slice(), &[10]);
+    ///
+    /// let err = v.take_back(3).unwrap_err();
+    /// assert_eq!(err.needed(), 3);
+    /// assert_eq!(err.len(), 1);
+    /// ```
+    pub fn take_back(&mut self, n: usize) -> Result<Self, UnexpectedEndError> {
+        let len = self.len();
+        if n > len {
+            return Err(UnexpectedEndError::new(n, len));
+        }
+        let mut back = self.inner.by_ref().rev().take(n).collect::<Vec<_>>();
+        back.reverse();
+        Ok(Self::new(back))
+    }
+
+    /// Views the elements left in the tranche as a slice.
+    pub fn as_slice(&self) -> &[T] {
+        self.inner.as_slice()
+    }
+
+    /// Views the elements left in the tranche as a mutable slice.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self.inner.as_mut_slice()
+    }
+
+    /// Borrows the elements left as a tranche.
+    ///
+    /// Taking things out of the returned tranche doesn't affect `self`.
+    pub fn as_tranche(&self) -> Tranche<'_, T> {
+        Tranche::new(self.as_slice())
+    }
+
+    /// Consumes the tranche, returning the elements left as a vector.
+    pub fn into_vec(self) -> Vec<T> {
+        self.inner.collect()
+    }
+}
+
+impl<T> Clone for VecTranche<T>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        let inner = self.inner.clone();
+        Self { inner }
+    }
+}
+
+impl<T> Default for VecTranche<T> {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl<T> From<Vec<T>> for VecTranche<T> {
+    fn from(vec: Vec<T>) -> Self {
+        Self::new(vec)
+    }
+}
+
+impl<T> fmt::Debug for VecTranche<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        self.as_slice().fmt(fmt)
+    }
+}
@@ -0,0 +1,452 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::convert::TryFrom;
+use core::fmt;
+
+use crate::core::UnexpectedEndError;
+#[cfg(feature = "alloc")]
+use crate::core::{BasedBufTranche, OverflowError};
+
+macro_rules! slice_writer_putter {
+    ($ty:ident $endian:tt $take:ident $from:ident $put:ident $to:ident $expect:ident) => {
+        taker_with_computed_doc! {
+            /// Puts a
+            #[doc = concat!("`", stringify!($ty), "`")]
+            /// at the front of the unwritten tail in
+            #[doc = $endian]
+            /// endian order.
+            ///
+            /// Returns `Err(_)` if `self` doesn't have enough room left, in
+            /// which case nothing is written.
+            #[inline]
+            pub fn $put(&mut self, value: $ty) -> Result<(), UnexpectedEndError> {
+                self.put_slice(&value.$to())
+            }
+        }
+    };
+}
+
+/// A writer of bytes into a fixed mutable buffer.
+///
+/// Slice writers are the writing counterpart of buffer tranches: bytes are
+/// put at the front of the unwritten tail of the buffer, and the writer keeps
+/// track of how many bytes were written so far.
+///
+/// This type implements `core::fmt::Write`, and `std::io::Write` when the
+/// `std` feature is enabled.
+pub struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    offset: usize,
+}
+
+impl<'a> SliceWriter<'a> {
+    /// Creates a new slice writer over the given buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tranche::SliceWriter;
+    /// let mut buf = [0; 16];
+    /// let writer = SliceWriter::new(&mut buf);
+    /// assert_eq!(writer.remaining(), 16);
+    /// ```
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self { buf, offset: 0 }
+    }
+
+    /// Returns how many bytes were written so far.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns how many bytes can still be written.
+    pub fn remaining(&self) -> usize {
+        self.buf.len() - self.offset
+    }
+
+    /// Returns `true` if no more bytes can be written.
+    pub fn is_full(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// Views the bytes written so far as a slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tranche::SliceWriter;
+    /// let mut buf = [0; 4];
+    /// let mut writer = SliceWriter::new(&mut buf);
+    /// writer.put_u16_be(0xcafe).unwrap();
+    /// assert_eq!(writer.written(), &[0xca, 0xfe]);
+    /// ```
+    pub fn written(&self) -> &[u8] {
+        &self.buf[..self.offset]
+    }
+
+    /// Views the unwritten tail of the buffer as a mutable slice.
+    ///
+    /// Writing into the tail does not advance the writer, see `advance`.
+    pub fn tail(&mut self) -> &mut [u8] {
+        &mut self.buf[self.offset..]
+    }
+
+    /// Marks the first `n` bytes of the unwritten tail as written.
+    ///
+    /// Returns `Err(_)` if fewer than `n` bytes remain.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tranche::SliceWriter;
+    /// let mut buf = [0; 4];
+    /// let mut writer = SliceWriter::new(&mut buf);
+    /// writer.tail()[..3].copy_from_slice(b"abc");
+    /// writer.advance(3).unwrap();
+    /// assert_eq!(writer.written(), b"abc");
+    ///
+    /// let err = writer.advance(2).unwrap_err();
+    /// assert_eq!(err.needed(), 2);
+    /// assert_eq!(err.len(), 1);
+    /// ```
+    pub fn advance(&mut self, n: usize) -> Result<(), UnexpectedEndError> {
+        let len = self.remaining();
+        if n > len {
+            return Err(UnexpectedEndError::new(n, len));
+        }
+        self.offset += n;
+        Ok(())
+    }
+
+    /// Consumes the writer, returning the bytes written so far.
+    pub fn into_written(self) -> &'a mut [u8] {
+        &mut self.buf[..self.offset]
+    }
+
+    /// Puts all the given bytes at the front of the unwritten tail.
+    ///
+    /// Returns `Err(_)` if `self` doesn't have enough room left, in which
+    /// case nothing is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tranche::SliceWriter;
+    /// let mut buf = [0; 4];
+    /// let mut writer = SliceWriter::new(&mut buf);
+    /// writer.put_slice(b"abc").unwrap();
+    ///
+    /// let err = writer.put_slice(b"de").unwrap_err();
+    /// assert_eq!(err.needed(), 2);
+    /// assert_eq!(err.len(), 1);
+    /// assert_eq!(writer.written(), b"abc");
+    /// ```
+    pub fn put_slice(&mut self, bytes: &[u8]) -> Result<(), UnexpectedEndError> {
+        let len = self.remaining();
+        if bytes.len() > len {
+            return Err(UnexpectedEndError::new(bytes.len(), len));
+        }
+        let end = self.offset + bytes.len();
+        self.buf[self.offset..end].copy_from_slice(bytes);
+        self.offset = end;
+        Ok(())
+    }
+
+    /// Puts a `u8` at the front of the unwritten tail.
+    ///
+    /// Returns `Err(_)` if `self` is full.
+    pub fn put_u8(&mut self, value: u8) -> Result<(), UnexpectedEndError> {
+        self.put_slice(&[value])
+    }
+
+    /// Puts an `i8` at the front of the unwritten tail.
+    ///
+    /// Returns `Err(_)` if `self` is full.
+    pub fn put_i8(&mut self, value: i8) -> Result<(), UnexpectedEndError> {
+        self.put_u8(value as u8)
+    }
+
+    call_for_each_taker!(slice_writer_putter);
+}
+
+impl fmt::Write for SliceWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.put_slice(s.as_bytes()).map_err(|_| fmt::Error)
+    }
+}
+
+impl fmt::Debug for SliceWriter<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("SliceWriter")
+            .field("written", &self.written())
+            .field("remaining", &self.remaining())
+            .finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+macro_rules! vec_writer_putter {
+    ($ty:ident $endian:tt $take:ident $from:ident $put:ident $to:ident $expect:ident) => {
+        taker_with_computed_doc! {
+            /// Puts a
+            #[doc = concat!("`", stringify!($ty), "`")]
+            /// at the end of the buffer in
+            #[doc = $endian]
+            /// endian order.
+            ///
+            /// Returns the offset at which the value was written.
+            #[inline]
+            pub fn $put(&mut self, value: $ty) -> usize {
+                self.put_slice(&value.$to())
+            }
+        }
+    };
+}
+
+/// A writer of bytes into a growable buffer.
+///
+/// This is the growable counterpart of [`SliceWriter`](struct.SliceWriter.html),
+/// so its methods never fail for lack of room. Every `put_*` method returns
+/// the offset at which the value was written, which is the same as what
+/// `BasedBufTranche::offset` returns when reading it back.
+///
+/// Lengths of bodies not known in advance can be reserved as placeholders
+/// with `reserve_len` and filled later with `fill_len`.
+///
+/// This type implements `core::fmt::Write`, and `std::io::Write` when the
+/// `std` feature is enabled.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default)]
+pub struct VecWriter {
+    buf: Vec<u8>,
+}
+
+/// The encoding of a length prefix reserved with `VecWriter::reserve_len`.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LenPrefix {
+    /// A single byte.
+    U8,
+    /// A `u16` in little endian order.
+    U16Le,
+    /// A `u16` in big endian order.
+    U16Be,
+    /// A `u32` in little endian order.
+    U32Le,
+    /// A `u32` in big endian order.
+    U32Be,
+    /// A LEB128 variable-length integer, as used by Protocol Buffers.
+    ///
+    /// The placeholder always takes 5 bytes so that offsets of whatever is
+    /// written after it stay valid, smaller lengths are padded with redundant
+    /// continuation bytes.
+    Varint,
+}
+
+/// A length prefix placeholder reserved with `VecWriter::reserve_len`.
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+#[must_use = "length placeholders should be filled with VecWriter::fill_len"]
+pub struct LenPlaceholder {
+    offset: usize,
+    prefix: LenPrefix,
+}
+
+#[cfg(feature = "alloc")]
+impl VecWriter {
+    /// Creates a new empty writer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new empty writer with at least the given capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buf: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Returns how many bytes were written so far.
+    pub fn offset(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Views the bytes written so far as a slice.
+    pub fn written(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// Views the bytes written so far as a based tranche, whose offsets
+    /// match the ones returned by the `put_*` methods.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tranche::VecWriter;
+    /// let mut writer = VecWriter::new();
+    /// writer.put_u8(1);
+    /// let offset = writer.put_u32_le(2);
+    ///
+    /// let mut tranche = writer.as_tranche();
+    /// tranche.take_u8().unwrap();
+    /// assert_eq!(tranche.offset(), offset);
+    /// assert_eq!(tranche.take_u32_le().unwrap(), 2);
+    /// ```
+    pub fn as_tranche(&self) -> BasedBufTranche<'_> {
+        BasedBufTranche::new(&self.buf)
+    }
+
+    /// Consumes the writer, returning the bytes written so far.
+    pub fn into_vec(self) -> Vec<u8> {
+        self.buf
+    }
+
+    /// Puts all the given bytes at the end of the buffer.
+    ///
+    /// Returns the offset at which the bytes were written.
+    pub fn put_slice(&mut self, bytes: &[u8]) -> usize {
+        let offset = self.offset();
+        self.buf.extend_from_slice(bytes);
+        offset
+    }
+
+    /// Puts a `u8` at the end of the buffer.
+    ///
+    /// Returns the offset at which the value was written.
+    pub fn put_u8(&mut self, value: u8) -> usize {
+        let offset = self.offset();
+        self.buf.push(value);
+        offset
+    }
+
+    /// Puts an `i8` at the end of the buffer.
+    ///
+    /// Returns the offset at which the value was written.
+    pub fn put_i8(&mut self, value: i8) -> usize {
+        self.put_u8(value as u8)
+    }
+
+    call_for_each_taker!(vec_writer_putter);
+
+    /// Reserves room for a length prefix, to be filled with `fill_len` once
+    /// the body that follows it is written.
+    ///
+    /// Placeholders can be nested and filled in any order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tranche::{LenPrefix, VecWriter};
+    /// let mut writer = VecWriter::new();
+    /// let outer = writer.reserve_len(LenPrefix::U16Be);
+    /// writer.put_u8(0xff);
+    /// let inner = writer.reserve_len(LenPrefix::U8);
+    /// writer.put_slice(b"abc");
+    /// assert_eq!(writer.fill_len(inner).unwrap(), 3);
+    /// assert_eq!(writer.fill_len(outer).unwrap(), 5);
+    ///
+    /// assert_eq!(writer.written(), &[0, 5, 0xff, 3, b'a', b'b', b'c']);
+    /// ```
+    pub fn reserve_len(&mut self, prefix: LenPrefix) -> LenPlaceholder {
+        let offset = self.offset();
+        self.buf.resize(offset + prefix.width(), 0);
+        LenPlaceholder { offset, prefix }
+    }
+
+    /// Fills a length prefix placeholder with the number of bytes written
+    /// since it was reserved.
+    ///
+    /// Returns that length, or `Err(_)` if it doesn't fit in the prefix, in
+    /// which case the placeholder is left zeroed.
+    ///
+    /// Panics if the placeholder lies past the bytes written so far. A
+    /// placeholder reserved by another writer is not detected otherwise, and
+    /// overwrites whatever bytes sit at its offset in this one.
+    pub fn fill_len(&mut self, placeholder: LenPlaceholder) -> Result<usize, OverflowError> {
+        let body_offset = placeholder.body_offset();
+        assert!(
+            body_offset <= self.offset(),
+            "length placeholder is out of bounds",
+        );
+        let len = self.offset() - body_offset;
+        let dest = &mut self.buf[placeholder.offset..body_offset];
+        match placeholder.prefix {
+            LenPrefix::U8 => dest.copy_from_slice(&narrow::<u8>(len)?.to_le_bytes()),
+            LenPrefix::U16Le => dest.copy_from_slice(&narrow::<u16>(len)?.to_le_bytes()),
+            LenPrefix::U16Be => dest.copy_from_slice(&narrow::<u16>(len)?.to_be_bytes()),
+            LenPrefix::U32Le => dest.copy_from_slice(&narrow::<u32>(len)?.to_le_bytes()),
+            LenPrefix::U32Be => dest.copy_from_slice(&narrow::<u32>(len)?.to_be_bytes()),
+            LenPrefix::Varint => {
+                let mut value = narrow::<u32>(len)?;
+                let (last, rest) = dest.split_last_mut().unwrap();
+                for byte in rest {
+                    *byte = (value & 0x7f) as u8 | 0x80;
+                    value >>= 7;
+                }
+                *last = value as u8;
+            }
+        }
+        Ok(len)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl LenPrefix {
+    /// Returns how many bytes a placeholder with this encoding takes.
+    pub fn width(self) -> usize {
+        match self {
+            LenPrefix::U8 => 1,
+            LenPrefix::U16Le | LenPrefix::U16Be => 2,
+            LenPrefix::U32Le | LenPrefix::U32Be => 4,
+            LenPrefix::Varint => 5,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl LenPlaceholder {
+    /// Returns the offset of the placeholder itself.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the offset of the body following the placeholder.
+    pub fn body_offset(&self) -> usize {
+        self.offset + self.prefix.width()
+    }
+
+    /// Returns the encoding of the placeholder.
+    pub fn prefix(&self) -> LenPrefix {
+        self.prefix
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn narrow<T>(len: usize) -> Result<T, OverflowError>
+where
+    T: TryFrom<usize>,
+{
+    T::try_from(len).map_err(|_| OverflowError::new())
+}
+
+#[cfg(feature = "alloc")]
+impl From<Vec<u8>> for VecWriter {
+    fn from(buf: Vec<u8>) -> Self {
+        Self { buf }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Write for VecWriter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.put_slice(s.as_bytes());
+        Ok(())
+    }
+}